@@ -0,0 +1,131 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+
+/// How to print per-survivor annotations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AnnotationFormat {
+    /// GitHub Actions workflow commands (`::warning file=...::message`).
+    Github,
+
+    /// Compiler-style `file:line:col: warning: message` lines for problem matchers.
+    Compiler,
+}
+
+/// Render one annotation per surviving mutant, in id order.
+///
+/// Paths are the project root joined with the file, so they resolve from the directory
+/// zk-mutant was run in (usually the repository root in CI). Survivors whose source
+/// cannot be read are skipped.
+pub fn render_annotations(
+    project: &Project,
+    mutants: &[Mutant],
+    format: AnnotationFormat,
+) -> Vec<String> {
+    let mut survivors: Vec<&Mutant> = mutants
+        .iter()
+        .filter(|m| m.outcome == MutantOutcome::Survived)
+        .collect();
+    survivors.sort_by_key(|m| m.id);
+
+    let mut out = Vec::new();
+    for m in survivors {
+        let Some(text) = project.sources().get(&m.span.file) else {
+            continue;
+        };
+        let (Some((sl, sc)), Some((el, ec))) = (
+            text.line_col(m.span.start as usize),
+            text.line_col(m.span.end as usize),
+        ) else {
+            continue;
+        };
+
+        let file = display_path(project.root(), &m.span.file);
+        let place = match m.function_label() {
+            Some(f) => format!(" in `{f}`"),
+            None => String::new(),
+        };
+        let message = format!(
+            "replacing `{}` with `{}`{place} did not fail any test",
+            m.original_snippet, m.mutated_snippet
+        );
+
+        out.push(match format {
+            AnnotationFormat::Github => format!(
+                "::warning file={},line={sl},col={sc},endLine={el},endColumn={ec},title={}::{}",
+                escape_property(&file),
+                escape_property(&format!("Surviving mutant #{} ({})", m.id, m.operator.name)),
+                escape_data(&format!("Mutant survived: {message}."))
+            ),
+            AnnotationFormat::Compiler => format!(
+                "{file}:{sl}:{sc}: warning: surviving mutant #{} ({}): {message}",
+                m.id, m.operator.name
+            ),
+        });
+    }
+    out
+}
+
+/// `root/file` with `.` components dropped and `/` separators.
+fn display_path(root: &Path, file: &Path) -> String {
+    let joined: PathBuf = root
+        .join(file)
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect();
+    joined.to_string_lossy().replace('\\', "/")
+}
+
+/// Escape a workflow command message.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+
+    #[test]
+    fn render_annotations_github_and_compiler() {
+        let project = Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load");
+        let mut mutants = discover_mutants(&project);
+        mutants[0].outcome = MutantOutcome::Killed;
+        mutants[1].outcome = MutantOutcome::Survived;
+
+        insta::assert_snapshot!(
+            "annotations",
+            [AnnotationFormat::Github, AnnotationFormat::Compiler]
+                .into_iter()
+                .flat_map(|f| render_annotations(&project, &mutants, f))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn display_path_drops_current_dir() {
+        assert_eq!(
+            display_path(Path::new("."), Path::new("src/main.nr")),
+            "src/main.nr"
+        );
+        assert_eq!(
+            display_path(Path::new("./circuits/a"), Path::new("src/main.nr")),
+            "circuits/a/src/main.nr"
+        );
+    }
+
+    #[test]
+    fn escape_property_encodes_separators() {
+        assert_eq!(escape_property("a:b,c%\n"), "a%3Ab%2Cc%25%0A");
+    }
+}
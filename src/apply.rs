@@ -0,0 +1,210 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::mutant::Mutant;
+use crate::patch::apply_checked_patch;
+use crate::project::Project;
+
+/// Guard file written to the project root while a mutant is applied to the working tree.
+pub const APPLIED_GUARD_FILE: &str = ".zk-mutant-applied.json";
+
+/// Contents of the guard file: enough to undo the edit made by `apply`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppliedMutant {
+    /// Id of the applied mutant.
+    pub id: u64,
+
+    /// Fingerprint of the applied mutant.
+    pub fingerprint: String,
+
+    /// Project-relative path of the edited file.
+    pub file: PathBuf,
+
+    /// Byte offset where the replacement starts.
+    pub start: u32,
+
+    /// Snippet that was replaced.
+    pub original_snippet: String,
+
+    /// Snippet written in its place.
+    pub mutated_snippet: String,
+}
+
+/// Path of the guard file for a project root.
+pub fn guard_path(project_root: &Path) -> PathBuf {
+    project_root.join(APPLIED_GUARD_FILE)
+}
+
+/// Read the guard file, if a mutant is currently applied.
+pub fn load_applied(project_root: &Path) -> Result<Option<AppliedMutant>> {
+    let path = guard_path(project_root);
+    let text = match fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {:?}", path)),
+    };
+    let applied =
+        serde_json::from_str(&text).with_context(|| format!("failed to parse {:?}", path))?;
+    Ok(Some(applied))
+}
+
+/// Write `m` into the working tree and record it in the guard file.
+///
+/// Refuses when another mutant is already applied or when the source no longer matches
+/// the mutant's span.
+pub fn apply_mutant(project: &Project, m: &Mutant) -> Result<AppliedMutant> {
+    if let Some(applied) = load_applied(project.root())? {
+        anyhow::bail!(
+            "mutant #{} is already applied to {:?}; run `zk-mutant apply --revert` first",
+            applied.id,
+            applied.file
+        );
+    }
+
+    let source = project
+        .find_source(&m.span.file)
+        .ok_or_else(|| anyhow::anyhow!("source file {:?} not part of project", m.span.file))?;
+    let original = source
+        .read_to_string()
+        .with_context(|| format!("failed to read {:?}", m.span.file))?;
+    if original.get(m.span.start as usize..m.span.end as usize) != Some(m.original_snippet.as_str())
+    {
+        anyhow::bail!(
+            "mutant {} no longer matches {:?} (source changed since discovery?)",
+            m.id,
+            m.span.file
+        );
+    }
+
+    let applied = AppliedMutant {
+        id: m.id,
+        fingerprint: m.fingerprint.clone(),
+        file: m.span.file.clone(),
+        start: m.span.start,
+        original_snippet: m.original_snippet.clone(),
+        mutated_snippet: m.mutated_snippet.clone(),
+    };
+
+    // Write the guard first so a failed edit never leaves an untracked mutation behind.
+    let guard = guard_path(project.root());
+    let json = serde_json::to_string_pretty(&applied).context("serialize guard file")?;
+    fs::write(&guard, json).with_context(|| format!("failed to write {:?}", guard))?;
+
+    let mutated = apply_checked_patch(&original, &m.span, &m.original_snippet, &m.mutated_snippet);
+    if let Err(e) = fs::write(source.path(), mutated) {
+        let _ = fs::remove_file(&guard);
+        return Err(e).with_context(|| format!("failed to write {:?}", source.path()));
+    }
+
+    Ok(applied)
+}
+
+/// Undo the mutant recorded in the guard file and remove the guard.
+///
+/// Only the mutated snippet is swapped back, so unrelated edits after the mutation are
+/// kept; edits that move or touch the snippet make this fail without changing anything.
+pub fn revert_applied(project_root: &Path) -> Result<AppliedMutant> {
+    let applied = load_applied(project_root)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "no applied mutant to revert ({:?} not found)",
+            guard_path(project_root)
+        )
+    })?;
+
+    let path = project_root.join(&applied.file);
+    let code = fs::read_to_string(&path).with_context(|| format!("failed to read {:?}", path))?;
+
+    let start = applied.start as usize;
+    let end = start + applied.mutated_snippet.len();
+    if code.get(start..end) != Some(applied.mutated_snippet.as_str()) {
+        anyhow::bail!(
+            "{:?} changed since mutant #{} was applied; expected {:?} at byte {}. \
+             Restore the file by hand and delete {:?}",
+            applied.file,
+            applied.id,
+            applied.mutated_snippet,
+            start,
+            guard_path(project_root)
+        );
+    }
+
+    let restored = format!(
+        "{}{}{}",
+        &code[..start],
+        applied.original_snippet,
+        &code[end..]
+    );
+    fs::write(&path, restored).with_context(|| format!("failed to write {:?}", path))?;
+
+    let guard = guard_path(project_root);
+    fs::remove_file(&guard).with_context(|| format!("failed to remove {:?}", guard))?;
+
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+    use tempfile::TempDir;
+
+    fn copy_fixture(dst: &Path) {
+        let src = Path::new("tests/fixtures/simple_noir");
+        for rel in ["Nargo.toml", "src/main.nr", "src/utils.nr"] {
+            let to = dst.join(rel);
+            fs::create_dir_all(to.parent().unwrap()).unwrap();
+            fs::copy(src.join(rel), to).unwrap();
+        }
+    }
+
+    #[test]
+    fn apply_then_revert_restores_the_file() {
+        let td = TempDir::new().unwrap();
+        copy_fixture(td.path());
+        let project = Project::from_root(td.path().to_path_buf()).unwrap();
+        let mutants = discover_mutants(&project);
+        let m = &mutants[1];
+
+        let main = td.path().join("src/main.nr");
+        let before = fs::read_to_string(&main).unwrap();
+
+        let applied = apply_mutant(&project, m).unwrap();
+        assert_eq!(applied.id, m.id);
+        assert!(
+            fs::read_to_string(&main)
+                .unwrap()
+                .contains("assert(x == y);")
+        );
+        assert_eq!(load_applied(td.path()).unwrap(), Some(applied));
+
+        let err = apply_mutant(&project, &mutants[0]).unwrap_err();
+        assert!(err.to_string().contains("already applied"), "{err}");
+
+        revert_applied(td.path()).unwrap();
+        assert_eq!(fs::read_to_string(&main).unwrap(), before);
+        assert!(!guard_path(td.path()).exists());
+
+        let err = revert_applied(td.path()).unwrap_err();
+        assert!(err.to_string().contains("no applied mutant"), "{err}");
+    }
+
+    #[test]
+    fn revert_refuses_when_the_snippet_moved() {
+        let td = TempDir::new().unwrap();
+        copy_fixture(td.path());
+        let project = Project::from_root(td.path().to_path_buf()).unwrap();
+        let mutants = discover_mutants(&project);
+        apply_mutant(&project, &mutants[1]).unwrap();
+
+        let main = td.path().join("src/main.nr");
+        let code = fs::read_to_string(&main).unwrap();
+        fs::write(&main, format!("// moved\n{code}")).unwrap();
+
+        let err = revert_applied(td.path()).unwrap_err();
+        assert!(err.to_string().contains("changed since mutant #2"), "{err}");
+        assert!(guard_path(td.path()).exists());
+    }
+}
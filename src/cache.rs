@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::hash::StableHasher;
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::patch::apply_checked_patch;
+use crate::project::Project;
+use crate::run_report::CacheStats;
+
+/// Bump when the key derivation or file layout changes; older files are ignored.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Cached outcome for a single mutant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub outcome: MutantOutcome,
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub killed_by: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+/// On-disk cache mapping a content hash of a mutant (and everything that can influence
+/// its outcome) to a previously observed outcome.
+///
+/// The key covers:
+/// - the full contents of the mutated file (with the mutation applied)
+/// - the full contents of every other source file in the project
+/// - `Nargo.toml`, the `nargo --version` string and the zk-mutant version
+/// - the mutant itself (file, span, operator, snippets)
+///
+/// The cache is all-or-nothing: any source edit invalidates every entry, because a change
+/// to a helper in another module can change which tests pass.
+///
+/// Only entries that were hit or inserted during this run are written back, so stale
+/// keys from earlier source revisions do not accumulate.
+#[derive(Debug)]
+pub struct ResultCache {
+    path: PathBuf,
+    context_hash: String,
+    entries: BTreeMap<String, CacheEntry>,
+    used: BTreeMap<String, CacheEntry>,
+    stats: CacheStats,
+}
+
+impl ResultCache {
+    /// Load the cache from `path` (missing or incompatible files start empty).
+    pub fn load(path: &Path, project: &Project, nargo_version: Option<&str>) -> Result<Self> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => match serde_json::from_str::<CacheFile>(&text) {
+                Ok(file) if file.version == CACHE_FORMAT_VERSION => file.entries,
+                _ => BTreeMap::new(),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {:?}", path)),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            context_hash: context_hash(project, nargo_version),
+            entries,
+            used: BTreeMap::new(),
+            stats: CacheStats::default(),
+        })
+    }
+
+    /// Compute the cache key for `mutant`, or `None` if its source file cannot be read.
+    pub fn key_for(&self, project: &Project, mutant: &Mutant) -> Option<String> {
+        let text = project.sources().get(&mutant.span.file)?;
+        if !text.matches(
+            mutant.span.start as usize,
+            mutant.span.end as usize,
+            &mutant.original_snippet,
+        ) {
+            return None;
+        }
+
+        let mutated = apply_checked_patch(
+            text.code(),
+            &mutant.span,
+            &mutant.original_snippet,
+            &mutant.mutated_snippet,
+        );
+
+        let mut h = StableHasher::new();
+        h.write_str(&self.context_hash);
+        h.write_str(&mutant.span.file.to_string_lossy());
+        h.write_str(&mutant.span.start.to_string());
+        h.write_str(&mutant.span.end.to_string());
+        h.write_str(&mutant.operator.name);
+        h.write_str(&mutant.original_snippet);
+        h.write_str(&mutant.mutated_snippet);
+        h.write_str(&mutated);
+        Some(h.finish_hex())
+    }
+
+    /// Look up a key, counting the result as a hit or a miss.
+    pub fn lookup(&mut self, key: Option<&str>) -> Option<CacheEntry> {
+        let Some((key, entry)) = key.and_then(|k| self.entries.get_key_value(k)) else {
+            self.stats.misses += 1;
+            return None;
+        };
+
+        self.stats.hits += 1;
+        self.used.insert(key.clone(), entry.clone());
+        Some(entry.clone())
+    }
+
+    /// Record the outcome of an executed mutant.
+    ///
+    /// Only killed/survived outcomes are stored; invalid outcomes are often caused by
+    /// transient environment problems and are always re-run.
+    pub fn insert(&mut self, key: String, mutant: &Mutant) {
+        if !matches!(
+            mutant.outcome,
+            MutantOutcome::Killed | MutantOutcome::Survived
+        ) {
+            return;
+        }
+
+        let entry = CacheEntry {
+            outcome: mutant.outcome.clone(),
+            duration_ms: mutant.duration_ms,
+            killed_by: mutant.killed_by.clone(),
+        };
+        self.entries.insert(key.clone(), entry.clone());
+        self.used.insert(key, entry);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats.clone()
+    }
+
+    /// Persist the entries hit or inserted during this run; everything else is dropped.
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {:?}", parent))?;
+            }
+        }
+
+        let file = CacheFile {
+            version: CACHE_FORMAT_VERSION,
+            entries: self.used.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file).context("serialize cache")?;
+        fs::write(&self.path, json).with_context(|| format!("failed to write {:?}", self.path))?;
+        Ok(())
+    }
+}
+
+/// Default cache location: a sibling of the output directory, so it survives rotation.
+///
+/// For example `mutants.out` -> `mutants.out.cache.json`.
+pub fn cache_path_for(out_dir: &Path) -> PathBuf {
+    let parent = out_dir.parent().unwrap_or_else(|| Path::new("."));
+    let name = out_dir
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mutants.out".to_string());
+    parent.join(format!("{name}.cache.json"))
+}
+
+/// Hash of everything shared by all mutants in a run (toolchain, manifest, sources).
+fn context_hash(project: &Project, nargo_version: Option<&str>) -> String {
+    let mut h = StableHasher::new();
+    h.write_str(env!("CARGO_PKG_VERSION"));
+    h.write_str(nargo_version.unwrap_or("<unknown>"));
+
+    let manifest = fs::read_to_string(project.root().join("Nargo.toml")).unwrap_or_default();
+    h.write_str(&manifest);
+
+    for (path, text) in project.sources().iter() {
+        h.write_str(&path.to_string_lossy());
+        h.write_str(text.code());
+    }
+
+    h.finish_hex()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+    use tempfile::TempDir;
+
+    fn fixture() -> Project {
+        Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load")
+    }
+
+    #[test]
+    fn key_is_stable_and_distinguishes_mutants() {
+        let project = fixture();
+        let mutants = discover_mutants(&project);
+        assert!(mutants.len() >= 2);
+
+        let td = TempDir::new().unwrap();
+        let cache =
+            ResultCache::load(&td.path().join("c.json"), &project, Some("nargo 1")).unwrap();
+
+        let k0 = cache.key_for(&project, &mutants[0]).unwrap();
+        let k0_again = cache.key_for(&project, &mutants[0]).unwrap();
+        let k1 = cache.key_for(&project, &mutants[1]).unwrap();
+
+        assert_eq!(k0, k0_again);
+        assert_ne!(k0, k1);
+    }
+
+    #[test]
+    fn key_changes_with_nargo_version() {
+        let project = fixture();
+        let mutants = discover_mutants(&project);
+
+        let td = TempDir::new().unwrap();
+        let path = td.path().join("c.json");
+        let a = ResultCache::load(&path, &project, Some("nargo 1")).unwrap();
+        let b = ResultCache::load(&path, &project, Some("nargo 2")).unwrap();
+
+        assert_ne!(
+            a.key_for(&project, &mutants[0]),
+            b.key_for(&project, &mutants[0])
+        );
+    }
+
+    #[test]
+    fn key_changes_when_any_source_file_changes() {
+        let td = TempDir::new().unwrap();
+        fs::write(
+            td.path().join("Nargo.toml"),
+            "[package]\nname = \"keys\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(td.path().join("src")).unwrap();
+        fs::write(
+            td.path().join("src/main.nr"),
+            "mod helper;\nfn main(x: u64) { assert(x == helper::one()); }\n",
+        )
+        .unwrap();
+        fs::write(
+            td.path().join("src/helper.nr"),
+            "pub fn one() -> u64 { 1 }\n",
+        )
+        .unwrap();
+
+        let key_of_main_mutant = |project: &Project| {
+            let mutants = discover_mutants(project);
+            let m = mutants
+                .iter()
+                .find(|m| m.span.file == Path::new("src/main.nr"))
+                .expect("main.nr should have a mutant");
+            let cache = ResultCache::load(&td.path().join("c.json"), project, None).unwrap();
+            cache.key_for(project, m).unwrap()
+        };
+
+        let before = key_of_main_mutant(&Project::from_root(td.path().to_path_buf()).unwrap());
+        fs::write(
+            td.path().join("src/helper.nr"),
+            "pub fn one() -> u64 { 2 }\n",
+        )
+        .unwrap();
+        let after = key_of_main_mutant(&Project::from_root(td.path().to_path_buf()).unwrap());
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn save_keeps_only_entries_used_in_this_run() {
+        let project = fixture();
+        let mut mutants = discover_mutants(&project);
+        mutants[0].outcome = MutantOutcome::Killed;
+        mutants[1].outcome = MutantOutcome::Survived;
+
+        let td = TempDir::new().unwrap();
+        let path = td.path().join("c.json");
+
+        let mut first = ResultCache::load(&path, &project, None).unwrap();
+        let k0 = first.key_for(&project, &mutants[0]).unwrap();
+        let k1 = first.key_for(&project, &mutants[1]).unwrap();
+        first.insert(k0.clone(), &mutants[0]);
+        first.insert(k1.clone(), &mutants[1]);
+        first.save().unwrap();
+
+        // The second run only touches the first mutant, so the other entry is pruned.
+        let mut second = ResultCache::load(&path, &project, None).unwrap();
+        assert!(second.lookup(Some(&k0)).is_some());
+        second.save().unwrap();
+
+        let mut third = ResultCache::load(&path, &project, None).unwrap();
+        assert!(third.lookup(Some(&k0)).is_some());
+        assert_eq!(third.lookup(Some(&k1)), None);
+    }
+
+    #[test]
+    fn save_and_reload_round_trips_killed_and_survived_only() {
+        let project = fixture();
+        let mut mutants = discover_mutants(&project);
+        mutants[0].outcome = MutantOutcome::Killed;
+        mutants[0].duration_ms = Some(5);
+        mutants[1].outcome = MutantOutcome::Invalid;
+
+        let td = TempDir::new().unwrap();
+        let path = td.path().join("c.json");
+
+        let mut cache = ResultCache::load(&path, &project, None).unwrap();
+        let k0 = cache.key_for(&project, &mutants[0]).unwrap();
+        let k1 = cache.key_for(&project, &mutants[1]).unwrap();
+        cache.insert(k0.clone(), &mutants[0]);
+        cache.insert(k1.clone(), &mutants[1]);
+        cache.save().unwrap();
+
+        let mut reloaded = ResultCache::load(&path, &project, None).unwrap();
+        assert_eq!(
+            reloaded.lookup(Some(&k0)),
+            Some(CacheEntry {
+                outcome: MutantOutcome::Killed,
+                duration_ms: Some(5),
+                killed_by: Vec::new(),
+            })
+        );
+        assert_eq!(reloaded.lookup(Some(&k1)), None);
+        assert_eq!(reloaded.lookup(None), None);
+
+        let stats = reloaded.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+    }
+
+    #[test]
+    fn cache_path_is_sibling_of_out_dir() {
+        assert_eq!(
+            cache_path_for(Path::new("proj/mutants.out")),
+            PathBuf::from("proj/mutants.out.cache.json")
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::mutant::{Mutant, MutantOutcome, OperatorCategory};
+use crate::nargo::package_name_from_nargo_toml;
+use crate::project::Project;
+use crate::run_report::{BaselineReport, MutationRunReport};
+use crate::source_map::SourceText;
+use crate::syntax::{enclosing_function, find_functions, tokenize};
+
+/// Command recorded in `argv` for every phase (nargo builds and tests in one step).
+const NARGO_TEST_ARGV: [&str; 2] = ["nargo", "test"];
+
+/// Exit code recorded for killed mutants. Per-mutant exit codes are not kept, so this is
+/// assumed rather than observed: `nargo test` exits with 1 when a test fails.
+const TEST_FAILURE_EXIT_CODE: i32 = 1;
+
+/// `outcomes.json` in cargo-mutants' schema.
+#[derive(Debug, Serialize)]
+pub struct LabOutcome {
+    pub outcomes: Vec<ScenarioOutcome>,
+    pub total_mutants: usize,
+    pub missed: usize,
+    pub caught: usize,
+    pub timeout: usize,
+    pub unviable: usize,
+    pub success: usize,
+    pub failure: usize,
+    /// RFC 3339 UTC timestamp.
+    pub start_time: String,
+    /// RFC 3339 UTC timestamp.
+    pub end_time: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScenarioOutcome {
+    pub scenario: Scenario,
+    pub log_path: String,
+    pub diff_path: Option<String>,
+    pub summary: SummaryOutcome,
+    pub phase_results: Vec<PhaseResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub enum Scenario {
+    Baseline,
+    Mutant(MutantScenario),
+}
+
+#[derive(Debug, Serialize)]
+pub struct MutantScenario {
+    pub package: String,
+    pub file: String,
+    pub function: Option<FunctionInfo>,
+    pub span: Span,
+    pub replacement: String,
+    pub genre: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionInfo {
+    pub function_name: String,
+    pub return_type: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Span {
+    pub start: LineColumn,
+    pub end: LineColumn,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SummaryOutcome {
+    Success,
+    CaughtMutant,
+    MissedMutant,
+    Unviable,
+    Timeout,
+    Failure,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PhaseResult {
+    pub phase: &'static str,
+    /// Seconds.
+    pub duration: f64,
+    pub process_status: ProcessStatus,
+    pub argv: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub enum ProcessStatus {
+    Success,
+    Failure(i32),
+    Timeout,
+}
+
+/// Write `outcomes.json` in cargo-mutants' schema (see [`build_lab_outcome`]).
+pub fn write_outcomes_json(
+    out_dir: &Path,
+    project: &Project,
+    report: &MutationRunReport,
+    start_time: SystemTime,
+    end_time: SystemTime,
+) -> Result<()> {
+    let lab = build_lab_outcome(project, report, start_time, end_time);
+    let path = out_dir.join("outcomes.json");
+    let json = serde_json::to_string_pretty(&lab).context("serialize outcomes.json")?;
+    fs::write(&path, json).with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// Convert a run report into cargo-mutants' `outcomes.json` shape.
+///
+/// The baseline comes first, then every executed mutant in id order, each with a single
+/// `Test` phase for its `nargo test` run: nargo builds and tests in one step, so no
+/// separate `Build` phase is reported. `log_path` is the run-wide `log` for the baseline
+/// and `mutants/<id>/nargo.log` for mutants.
+///
+/// Invalid mutants never produced a `nargo test` result (the temp project could not be
+/// prepared or nargo could not be started). cargo-mutants has no such outcome, so they
+/// are reported as `Unviable` with no phases.
+pub fn build_lab_outcome(
+    project: &Project,
+    report: &MutationRunReport,
+    start_time: SystemTime,
+    end_time: SystemTime,
+) -> LabOutcome {
+    let package = package_name_from_nargo_toml(project.root())
+        .ok()
+        .flatten()
+        .or_else(|| {
+            project
+                .root()
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+
+    let mut outcomes = vec![baseline_outcome(&report.baseline)];
+
+    let mut executed: Vec<&Mutant> = report
+        .mutants
+        .iter()
+        .filter(|m| m.outcome != MutantOutcome::NotRun)
+        .collect();
+    executed.sort_by_key(|m| m.id);
+
+    for m in executed {
+        let Some(text) = project.sources().get(&m.span.file) else {
+            continue;
+        };
+        if let Some(outcome) = mutant_outcome(&package, text, m) {
+            outcomes.push(outcome);
+        }
+    }
+
+    let mut lab = LabOutcome {
+        outcomes: Vec::new(),
+        total_mutants: report.executed,
+        missed: 0,
+        caught: 0,
+        timeout: 0,
+        unviable: 0,
+        success: 0,
+        failure: 0,
+        start_time: rfc3339_utc(start_time),
+        end_time: rfc3339_utc(end_time),
+    };
+    for o in &outcomes {
+        match o.summary {
+            SummaryOutcome::Success => lab.success += 1,
+            SummaryOutcome::CaughtMutant => lab.caught += 1,
+            SummaryOutcome::MissedMutant => lab.missed += 1,
+            SummaryOutcome::Unviable => lab.unviable += 1,
+            SummaryOutcome::Timeout => lab.timeout += 1,
+            SummaryOutcome::Failure => lab.failure += 1,
+        }
+    }
+    lab.outcomes = outcomes;
+    lab
+}
+
+fn baseline_outcome(baseline: &BaselineReport) -> ScenarioOutcome {
+    let (summary, process_status) = if baseline.success {
+        (SummaryOutcome::Success, ProcessStatus::Success)
+    } else {
+        (
+            SummaryOutcome::Failure,
+            ProcessStatus::Failure(baseline.exit_code.unwrap_or(TEST_FAILURE_EXIT_CODE)),
+        )
+    };
+
+    ScenarioOutcome {
+        scenario: Scenario::Baseline,
+        log_path: "log".to_string(),
+        diff_path: None,
+        summary,
+        phase_results: vec![test_phase(baseline.duration_ms, process_status)],
+    }
+}
+
+fn mutant_outcome(package: &str, text: &SourceText, m: &Mutant) -> Option<ScenarioOutcome> {
+    let code = text.code();
+    let span = span_of(text, m.span.start as usize, m.span.end as usize)?;
+
+    let functions = find_functions(code, &tokenize(code));
+    let function = enclosing_function(&functions, m.span.start as usize).and_then(|f| {
+        let header = &code[f.start..f.body.start];
+        let return_type = header
+            .find("->")
+            .map(|i| header[i..].split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+        Some(FunctionInfo {
+            function_name: f.name.clone(),
+            return_type,
+            span: span_of(text, f.start, f.body.end)?,
+        })
+    });
+
+    let duration_ms = m.duration_ms.unwrap_or(0);
+    let (summary, phase_results) = match m.outcome {
+        MutantOutcome::Killed => (
+            SummaryOutcome::CaughtMutant,
+            vec![test_phase(
+                duration_ms,
+                ProcessStatus::Failure(TEST_FAILURE_EXIT_CODE),
+            )],
+        ),
+        MutantOutcome::Survived => (
+            SummaryOutcome::MissedMutant,
+            vec![test_phase(duration_ms, ProcessStatus::Success)],
+        ),
+        MutantOutcome::Timeout => (
+            SummaryOutcome::Timeout,
+            vec![test_phase(duration_ms, ProcessStatus::Timeout)],
+        ),
+        MutantOutcome::Invalid => (SummaryOutcome::Unviable, Vec::new()),
+        MutantOutcome::NotRun => return None,
+    };
+
+    Some(ScenarioOutcome {
+        scenario: Scenario::Mutant(MutantScenario {
+            package: package.to_string(),
+            file: m.span.file.to_string_lossy().replace('\\', "/"),
+            function,
+            span,
+            replacement: m.mutated_snippet.clone(),
+            genre: genre(&m.operator.category),
+        }),
+        log_path: format!("mutants/{:06}/nargo.log", m.id),
+        diff_path: Some(format!("diff/{:06}.diff", m.id)),
+        summary,
+        phase_results,
+    })
+}
+
+/// cargo-mutants genre for an operator category.
+///
+/// cargo-mutants has no genre for constant replacement; `FnValue` (replace a value) is
+/// the closest.
+fn genre(category: &OperatorCategory) -> &'static str {
+    match category {
+        OperatorCategory::Condition | OperatorCategory::Arithmetic => "BinaryOperator",
+        OperatorCategory::BooleanConnective => "UnaryOperator",
+        OperatorCategory::Constant => "FnValue",
+    }
+}
+
+fn test_phase(duration_ms: u64, process_status: ProcessStatus) -> PhaseResult {
+    PhaseResult {
+        phase: "Test",
+        duration: duration_ms as f64 / 1000.0,
+        process_status,
+        argv: NARGO_TEST_ARGV.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Format `time` as an RFC 3339 UTC timestamp with second precision, for example
+/// `2024-05-01T12:30:00Z`.
+fn rfc3339_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's `civil_from_days`).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+fn span_of(text: &SourceText, start: usize, end: usize) -> Option<Span> {
+    let (sl, sc) = text.line_col(start)?;
+    let (el, ec) = text.line_col(end)?;
+    Some(Span {
+        start: LineColumn {
+            line: sl,
+            column: sc,
+        },
+        end: LineColumn {
+            line: el,
+            column: ec,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_report;
+    use std::time::Duration;
+
+    #[test]
+    fn build_lab_outcome_snapshot_fixture() {
+        let (project, mut report) = fixture_report(&[
+            MutantOutcome::Killed,
+            MutantOutcome::Survived,
+            MutantOutcome::Invalid,
+            MutantOutcome::Timeout,
+        ]);
+        for m in &mut report.mutants {
+            m.duration_ms = Some(1250);
+        }
+        report.baseline.duration_ms = 500;
+
+        let start = UNIX_EPOCH + Duration::from_secs(1_714_566_600);
+        let end = start + Duration::from_secs(6);
+        insta::assert_json_snapshot!(
+            "cargo_mutants_outcomes",
+            build_lab_outcome(&project, &report, start, end)
+        );
+    }
+
+    #[test]
+    fn rfc3339_utc_formats_civil_dates() {
+        let at = |secs| rfc3339_utc(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(1_714_566_600), "2024-05-01T12:30:00Z");
+        assert_eq!(at(4_102_444_799), "2099-12-31T23:59:59Z");
+    }
+}
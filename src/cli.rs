@@ -1,26 +1,56 @@
-use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use serde::Serialize;
 
+use crate::annotations::{AnnotationFormat, render_annotations};
+use crate::apply::{apply_mutant, load_applied, revert_applied};
+use crate::cache::{ResultCache, cache_path_for};
+use crate::cargo_mutants;
+use crate::compare::{compare_runs, load_run_report, render_comparison};
+use crate::config::{Config, check_percentage};
+use crate::diff::DEFAULT_CONTEXT_LINES;
 use crate::discover::discover_mutants;
-use crate::mutant::Mutant;
+use crate::export::{select_mutants, write_patch_series};
+use crate::html;
+use crate::junit;
+use crate::known_survivors::KnownSurvivors;
+use crate::markdown;
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::mutant_dir::{self, DEFAULT_MAX_LOG_BYTES, LogCapture};
 use crate::nargo::{compiler_version_from_nargo_toml, nargo_version, run_nargo_test};
 use crate::options::Options;
-use crate::out;
+use crate::out::{self, OutcomesFormat};
+use crate::policy::{
+    PolicyViolation, Thresholds, ViolationKind, evaluate, path_is_under, survivors_violation,
+};
 use crate::project::Project;
-use crate::report::{format_mutant_with_location, print_all_mutants, print_surviving_mutants};
+use crate::report::{
+    format_mutant_with_location, outcome_label, print_all_mutants, print_surviving_mutants,
+    render_all_mutants, render_score_breakdown, render_surviving_mutants, score_label,
+};
 use crate::run_report::{BaselineReport, MutationRunReport, RunSummary};
-use crate::runner::run_all_mutants_in_temp;
-use crate::scan::ProjectOverview;
+use crate::runner::{RunnerOptions, run_all_mutants_in_temp};
+use crate::sarif;
+use crate::scan::{
+    FileScan, FunctionScan, MutationInventory, ProjectOverview, WeakTest, find_weak_tests,
+    scan_files, scan_functions,
+};
+use crate::show::{DEFAULT_SHOW_CONTEXT_LINES, find_mutant, render_mutant_in_context};
+use crate::source_map::UnreadableSource;
+use crate::stryker;
+use crate::suggest;
 use crate::ui::Ui;
 
 const EXIT_OK: i32 = 0;
 const EXIT_ERROR: i32 = 1;
 const EXIT_SURVIVORS: i32 = 2;
+const EXIT_SCORE: i32 = 3;
+const EXIT_UNSTABLE: i32 = 4;
 
 fn exit_code_for_survivors_policy(fail_on_survivors: bool, survived: usize) -> i32 {
     if fail_on_survivors && survived > 0 {
@@ -30,6 +60,42 @@ fn exit_code_for_survivors_policy(fail_on_survivors: bool, survived: usize) -> i
     }
 }
 
+/// Exit code for a set of violations: the lowest code among them wins, so survivors (2)
+/// take precedence over score thresholds (3), which take precedence over invalid or
+/// timed-out mutants (4).
+fn exit_code_for_violations(violations: &[PolicyViolation]) -> i32 {
+    violations
+        .iter()
+        .map(|v| match v.kind {
+            ViolationKind::Survivors => EXIT_SURVIVORS,
+            ViolationKind::MinScore | ViolationKind::PathMinScore => EXIT_SCORE,
+            ViolationKind::Invalid | ViolationKind::Timeout => EXIT_UNSTABLE,
+        })
+        .min()
+        .unwrap_or(EXIT_OK)
+}
+
+fn parse_percentage(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    check_percentage(value).map_err(|e| e.to_string())?;
+    Ok(value)
+}
+
+fn parse_outcome(s: &str) -> Result<MutantOutcome, String> {
+    [
+        MutantOutcome::Killed,
+        MutantOutcome::Survived,
+        MutantOutcome::Invalid,
+        MutantOutcome::Timeout,
+        MutantOutcome::NotRun,
+    ]
+    .into_iter()
+    .find(|o| outcome_label(o) == s.to_ascii_lowercase().replace('-', "_"))
+    .ok_or_else(|| {
+        format!("unknown outcome `{s}` (expected killed, survived, invalid, timeout or not_run)")
+    })
+}
+
 /// Top-level CLI arguments for the `zk-mutant` binary.
 #[derive(Debug, Parser)]
 #[command(
@@ -51,6 +117,10 @@ pub enum Command {
         /// Path to the Noir project root or any path inside it.
         #[arg(long, default_value = ".")]
         project: PathBuf,
+
+        /// Emit a machine-readable JSON report to stdout.
+        #[arg(long)]
+        json: bool,
     },
 
     /// Print toolchain + baseline `nargo test` diagnostics (copy/paste friendly).
@@ -107,10 +177,237 @@ pub enum Command {
         #[arg(long)]
         fail_on_survivors: bool,
 
+        /// Exit with code 3 if the mutation score is below this percentage
+        /// (overrides `thresholds.min_score` in the config file).
+        #[arg(long, value_name = "PCT", value_parser = parse_percentage)]
+        min_score: Option<f64>,
+
+        /// Exit with code 4 if any mutant is invalid.
+        #[arg(long)]
+        fail_on_invalid: bool,
+
+        /// Exit with code 4 if any mutant times out.
+        #[arg(long)]
+        fail_on_timeout: bool,
+
+        /// Kill `nargo test` for a mutant after this many seconds and record a timeout.
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+
+        /// Config file with thresholds (defaults to <project_root>/zk-mutant.json if present).
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+
+        /// Also write the SARIF log of surviving mutants (always written to
+        /// `mutants.sarif` in the output directory) to this path.
+        #[arg(long, value_name = "PATH")]
+        sarif: Option<PathBuf>,
+
+        /// Print one annotation per surviving mutant: GitHub Actions workflow commands
+        /// or compiler-style `file:line:col: warning:` lines.
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        annotations: Option<AnnotationFormat>,
+
+        /// Write a Markdown summary (for pull-request comments) to this path. It is also
+        /// appended to `$GITHUB_STEP_SUMMARY` when that is set.
+        #[arg(long, value_name = "PATH")]
+        markdown: Option<PathBuf>,
+
+        /// Schema of `outcomes.json`.
+        #[arg(long, value_enum, value_name = "FORMAT", default_value_t = OutcomesFormat::ZkMutant)]
+        outcomes_format: OutcomesFormat,
+
+        /// Unchanged lines of context around each change in `diff/*.diff`.
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_CONTEXT_LINES)]
+        diff_context: usize,
+
+        /// Keep at most this many trailing bytes of each mutant's `nargo test` stdout and
+        /// stderr in `mutants/<id>/nargo.log` (0 keeps everything).
+        #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_LOG_BYTES)]
+        max_log_bytes: usize,
+
+        /// JSON file of accepted survivors (by fingerprint); only survivors not listed
+        /// in it count for `--fail-on-survivors`.
+        #[arg(long, value_name = "FILE")]
+        survivors_baseline: Option<PathBuf>,
+
+        /// Rewrite the `--survivors-baseline` file to accept exactly this run's survivors.
+        #[arg(long, requires = "survivors_baseline")]
+        update_baseline: bool,
+
         /// Where to write run artifacts (defaults to <project_root>/mutants.out).
         #[arg(long)]
         out_dir: Option<PathBuf>,
+
+        /// Execute every mutant instead of reusing outcomes from the result cache
+        /// (stored next to the output directory, for example `mutants.out.cache.json`).
+        #[arg(long)]
+        no_cache: bool,
     },
+
+    /// Compare two run reports (`run.json`) and show what changed.
+    ///
+    /// Mutants are matched by fingerprint, so line shifts do not show up as changes.
+    Compare {
+        /// Older `run.json`.
+        old: PathBuf,
+
+        /// Newer `run.json`.
+        new: PathBuf,
+
+        /// Emit a machine-readable JSON report to stdout.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Re-render a previous run (`run.json`) in another format without re-running mutants.
+    Report {
+        /// Run report to read (for example `mutants.out/run.json`).
+        #[arg(long, value_name = "FILE")]
+        from: PathBuf,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Write to this file instead of stdout. For `html`, the directory that receives
+        /// `report/` (defaults to the directory containing the run report).
+        #[arg(long, short = 'o', value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Project root to read sources from (defaults to the root recorded in the report).
+        #[arg(long)]
+        project: Option<PathBuf>,
+
+        /// Only include mutants with this outcome (repeatable): killed, survived,
+        /// invalid, timeout or not_run.
+        #[arg(long, value_name = "OUTCOME", value_parser = parse_outcome)]
+        outcome: Vec<MutantOutcome>,
+
+        /// Only include mutants in this file or directory, for example `src/utils.nr`
+        /// or `src/merkle` (repeatable; whole path components must match).
+        #[arg(long, value_name = "PATH")]
+        file: Vec<String>,
+
+        /// List every mutant in `text` output.
+        #[arg(long, short = 'v')]
+        verbose: bool,
+
+        /// Unchanged lines of context around each change in `junit` failures.
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_CONTEXT_LINES)]
+        diff_context: usize,
+    },
+
+    /// Print one mutant with surrounding source, a caret under the span and the mutated line.
+    Show {
+        /// Mutant id (as printed by `list`) or fingerprint (a unique prefix is enough).
+        mutant: String,
+
+        /// Path to the Noir project root or any path inside it.
+        #[arg(long, default_value = ".")]
+        project: PathBuf,
+
+        /// Source lines to print before and after the mutated line.
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_SHOW_CONTEXT_LINES)]
+        context: usize,
+    },
+
+    /// Write one mutant into the working tree (undo with `apply --revert`).
+    ///
+    /// The edit is recorded in `.zk-mutant-applied.json` at the project root; only one
+    /// mutant can be applied at a time.
+    Apply {
+        /// Mutant id (as printed by `list`) or fingerprint (a unique prefix is enough).
+        #[arg(required_unless_present = "revert")]
+        mutant: Option<String>,
+
+        /// Restore the source changed by the previously applied mutant.
+        #[arg(long, conflicts_with = "mutant")]
+        revert: bool,
+
+        /// Path to the Noir project root or any path inside it.
+        #[arg(long, default_value = ".")]
+        project: PathBuf,
+    },
+
+    /// Write mutants as `git format-patch` style patches (one `.patch` per mutant).
+    ///
+    /// Apply them from the project root with `git am` or `git apply`.
+    Export {
+        /// Mutant ids to export, comma-separated or repeated (default: all).
+        #[arg(long, value_name = "ID", value_delimiter = ',')]
+        ids: Vec<u64>,
+
+        /// Only export mutants with this outcome (repeatable; needs `--from`).
+        #[arg(long, value_name = "OUTCOME", value_parser = parse_outcome, requires = "from")]
+        outcome: Vec<MutantOutcome>,
+
+        /// Take mutants and outcomes from a run report (for example `mutants.out/run.json`)
+        /// instead of discovering them.
+        #[arg(long, value_name = "FILE")]
+        from: Option<PathBuf>,
+
+        /// Project root to read sources from (defaults to the root recorded in the report,
+        /// or `.` without `--from`).
+        #[arg(long)]
+        project: Option<PathBuf>,
+
+        /// Directory to write the patches to.
+        #[arg(long, value_name = "DIR", default_value = "patches")]
+        out: PathBuf,
+
+        /// Unchanged lines of context around each change.
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_CONTEXT_LINES)]
+        diff_context: usize,
+    },
+}
+
+/// Output formats for `report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// The human-readable run summary.
+    Text,
+
+    /// The run report itself (`run.json` shape).
+    Json,
+
+    /// Static HTML report (`report/index.html`).
+    Html,
+
+    /// Markdown summary for pull-request comments.
+    Markdown,
+
+    /// JUnit XML.
+    Junit,
+
+    /// SARIF log of surviving mutants.
+    Sarif,
+}
+
+/// Describe a mutant that `zk-mutant apply` left in the working tree, if any.
+///
+/// An unreadable guard file is reported too: the tree may still be mutated.
+fn applied_mutant_notice(project_root: &Path) -> Option<String> {
+    match load_applied(project_root) {
+        Ok(None) => None,
+        Ok(Some(applied)) => Some(format!(
+            "mutant #{} is applied to {:?}; undo it with `zk-mutant apply --revert`",
+            applied.id, applied.file
+        )),
+        Err(e) => Some(format!(
+            "{e:#}; a mutant may still be applied (see `zk-mutant apply --revert`)"
+        )),
+    }
+}
+
+/// Warn about source files that could not be read; they get no mutants.
+fn warn_unreadable_sources(ui: &Ui, project: &Project) {
+    for source in project.sources().unreadable() {
+        ui.warn(format!(
+            "warning: skipping unreadable source file {:?}: {}",
+            source.file, source.error
+        ));
+    }
 }
 
 fn print_json_and_exit(report: &MutationRunReport, exit_code: i32) {
@@ -162,6 +459,24 @@ struct MutationListReport {
     error: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct ScanReport {
+    tool: &'static str,
+    version: &'static str,
+    project_root: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overview: Option<ProjectOverview>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inventory: Option<MutationInventory>,
+    files: Vec<FileScan>,
+    functions: Vec<FunctionScan>,
+    weak_tests: Vec<WeakTest>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unreadable_sources: Vec<UnreadableSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct PreflightReport {
     tool: &'static str,
@@ -259,16 +574,38 @@ pub fn run() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Command::Scan { project } => {
-            let ui = Ui::new(false);
+        Command::Scan { project, json } => {
+            let ui = Ui::new(json);
             let options = Options::new(project);
 
             ui.title("zk-mutant: scan");
             ui.line(format!("project: {:?}", options.project_root));
+            if let Some(notice) = applied_mutant_notice(&options.project_root) {
+                ui.warn(format!("warning: {notice}"));
+            }
 
             let project = match Project::from_root(options.project_root.clone()) {
                 Ok(p) => p,
                 Err(e) => {
+                    if json {
+                        let report = ScanReport {
+                            tool: "zk-mutant",
+                            version: env!("CARGO_PKG_VERSION"),
+                            project_root: options.project_root.clone(),
+                            overview: None,
+                            inventory: None,
+                            files: Vec::new(),
+                            functions: Vec::new(),
+                            weak_tests: Vec::new(),
+                            unreadable_sources: Vec::new(),
+                            error: Some(format!("failed to load Noir project: {e}")),
+                        };
+                        let txt =
+                            serde_json::to_string_pretty(&report).expect("serialize scan report");
+                        println!("{txt}");
+                        std::process::exit(EXIT_ERROR);
+                    }
+
                     ui.error(format!(
                         "failed to load Noir project at {:?}: {e}",
                         options.project_root
@@ -276,12 +613,36 @@ pub fn run() -> Result<()> {
                     return Err(e);
                 }
             };
+            warn_unreadable_sources(&ui, &project);
 
             let overview = ProjectOverview::from_project(&project);
-            print_scan_summary(&overview, &ui);
-
             let mutants = discover_mutants(&project);
-            print_mutation_inventory(&mutants, &ui);
+            let inventory = MutationInventory::from_mutants(&mutants);
+            let functions = scan_functions(&project, &mutants);
+            let weak_tests = find_weak_tests(&project);
+
+            if json {
+                let report = ScanReport {
+                    tool: "zk-mutant",
+                    version: env!("CARGO_PKG_VERSION"),
+                    project_root: options.project_root.clone(),
+                    files: scan_files(&project, &inventory),
+                    functions,
+                    weak_tests,
+                    overview: Some(overview),
+                    inventory: Some(inventory),
+                    unreadable_sources: project.sources().unreadable().to_vec(),
+                    error: None,
+                };
+                let txt = serde_json::to_string_pretty(&report).expect("serialize scan report");
+                println!("{txt}");
+                return Ok(());
+            }
+
+            print_scan_summary(&overview, &ui);
+            print_mutation_inventory(&inventory, &ui);
+            print_function_heatmap(&functions, &ui);
+            print_weak_tests(&weak_tests, &ui);
 
             Ok(())
         }
@@ -407,6 +768,9 @@ pub fn run() -> Result<()> {
 
             ui.title("zk-mutant: list");
             ui.line(format!("project: {:?}", project_root));
+            if let Some(notice) = applied_mutant_notice(&project_root) {
+                ui.warn(format!("warning: {notice}"));
+            }
 
             // toolchain/version awareness (non-JSON output only).
             if !json {
@@ -440,6 +804,7 @@ pub fn run() -> Result<()> {
                     return Err(e);
                 }
             };
+            warn_unreadable_sources(&ui, &project);
 
             let discovered_mutants = discover_mutants(&project);
             let discovered = discovered_mutants.len();
@@ -517,12 +882,45 @@ pub fn run() -> Result<()> {
             limit,
             json,
             fail_on_survivors,
+            min_score,
+            fail_on_invalid,
+            fail_on_timeout,
+            timeout,
+            config,
+            sarif,
+            annotations,
+            markdown,
+            outcomes_format,
+            diff_context,
+            max_log_bytes,
+            survivors_baseline,
+            update_baseline,
             out_dir,
+            no_cache,
         } => {
+            let started_at = SystemTime::now();
             let mut ui = Ui::new(json);
             let options = Options::new(project);
             let project_root = options.project_root.clone();
 
+            // A mutant left in the working tree would make every result meaningless.
+            if let Some(notice) = applied_mutant_notice(&project_root) {
+                let error = format!("refusing to run: {notice}");
+                if json {
+                    let report = MutationRunReport::failure(
+                        project_root.clone(),
+                        BaselineReport {
+                            success: false,
+                            exit_code: None,
+                            duration_ms: 0,
+                        },
+                        error.clone(),
+                    );
+                    print_json_and_exit(&report, EXIT_ERROR);
+                }
+                anyhow::bail!(error);
+            }
+
             // Output directory (rotate + create)
             let out_dir = out_dir.unwrap_or_else(|| project_root.join("mutants.out"));
             if let Err(e) = prepare_out_dir(&out_dir) {
@@ -578,6 +976,65 @@ pub fn run() -> Result<()> {
                     return Err(e);
                 }
             };
+            warn_unreadable_sources(&ui, &project);
+
+            // Known-survivors baseline (fail fast before running anything).
+            let known_survivors = match survivors_baseline.as_deref() {
+                Some(path) if update_baseline && !path.exists() => Some(KnownSurvivors::default()),
+                Some(path) => match KnownSurvivors::load(path) {
+                    Ok(k) => Some(k),
+                    Err(e) => {
+                        let report = MutationRunReport::failure(
+                            project_root.clone(),
+                            BaselineReport {
+                                success: false,
+                                exit_code: None,
+                                duration_ms: 0,
+                            },
+                            format!("failed to load survivors baseline: {e}"),
+                        );
+                        let _ = write_run_json(&out_dir, &report);
+
+                        if json {
+                            print_json_and_exit(&report, EXIT_ERROR);
+                        }
+
+                        ui.error(format!("failed to load survivors baseline {:?}: {e}", path));
+                        return Err(e);
+                    }
+                },
+                None => None,
+            };
+
+            // Thresholds: config file values, overridden by CLI flags.
+            let config = match Config::load(project.root(), config.as_deref()) {
+                Ok(c) => c,
+                Err(e) => {
+                    let report = MutationRunReport::failure(
+                        project_root.clone(),
+                        BaselineReport {
+                            success: false,
+                            exit_code: None,
+                            duration_ms: 0,
+                        },
+                        format!("failed to load config: {e}"),
+                    );
+                    let _ = write_run_json(&out_dir, &report);
+
+                    if json {
+                        print_json_and_exit(&report, EXIT_ERROR);
+                    }
+
+                    ui.error(format!("failed to load config: {e:#}"));
+                    return Err(e);
+                }
+            };
+            let thresholds = Thresholds::new(
+                &config.thresholds,
+                min_score,
+                fail_on_invalid,
+                fail_on_timeout,
+            );
 
             // Baseline `nargo test` run before mutation testing.
             let baseline_result = match run_nargo_test(project.root()) {
@@ -643,6 +1100,8 @@ pub fn run() -> Result<()> {
             // Discover mutation opportunities.
             let mut mutants = discover_mutants(&project);
             let discovered = mutants.len();
+            let discovered_fingerprints: BTreeSet<String> =
+                mutants.iter().map(|m| m.fingerprint.clone()).collect();
 
             // Persist discovery list (pre-limit) as mutants.json
             if let Err(e) = out::write_mutants_json(&out_dir, &mutants) {
@@ -652,7 +1111,7 @@ pub fn run() -> Result<()> {
             ui.line(format!("discovered {} mutants", discovered));
 
             if discovered == 0 {
-                let report = MutationRunReport::success(
+                let mut report = MutationRunReport::success(
                     project_root.clone(),
                     0,
                     0,
@@ -660,6 +1119,7 @@ pub fn run() -> Result<()> {
                     RunSummary::default(),
                     Vec::new(),
                 );
+                report.unreadable_sources = project.sources().unreadable().to_vec();
                 let _ = write_run_json(&out_dir, &report);
 
                 if json {
@@ -670,6 +1130,8 @@ pub fn run() -> Result<()> {
                 return Ok(());
             }
 
+            // Mutants cut by `--limit`; still listed (as skipped) in junit.xml.
+            let mut cut_by_limit = Vec::new();
             if let Some(limit) = limit {
                 if limit == 0 {
                     let report = MutationRunReport::success(
@@ -689,7 +1151,7 @@ pub fn run() -> Result<()> {
                     ui.line("mutant limit is 0, exiting");
                     return Ok(());
                 }
-                mutants.truncate(limit);
+                cut_by_limit = mutants.split_off(limit.min(mutants.len()));
                 ui.line(format!(
                     "running {} mutants (of {})",
                     mutants.len(),
@@ -697,14 +1159,86 @@ pub fn run() -> Result<()> {
                 ));
             }
 
+            // Result cache: reuse outcomes for mutants whose inputs did not change.
+            let mut cache = if no_cache {
+                None
+            } else {
+                let nargo_v = toolchain
+                    .nargo_version
+                    .clone()
+                    .or_else(|| nargo_version().ok());
+                match ResultCache::load(&cache_path_for(&out_dir), &project, nargo_v.as_deref()) {
+                    Ok(c) => Some(c),
+                    Err(e) => {
+                        ui.warn(format!(
+                            "failed to load result cache, running uncached: {e}"
+                        ));
+                        None
+                    }
+                }
+            };
+
             // Run all mutants sequentially (naive implementation).
             let executed = mutants.len();
-            let summary = run_all_mutants_in_temp(&project, &mut mutants, &mut ui)?;
+            let runner_options = RunnerOptions {
+                timeout: timeout.map(std::time::Duration::from_secs),
+                logs: Some(LogCapture {
+                    out_dir: out_dir.clone(),
+                    max_bytes: max_log_bytes,
+                }),
+            };
+            let summary = run_all_mutants_in_temp(
+                &project,
+                &mut mutants,
+                &runner_options,
+                cache.as_mut(),
+                &mut ui,
+            )?;
+
+            if let Some(c) = cache.as_ref() {
+                if let Err(e) = c.save() {
+                    ui.warn(format!("failed to write result cache: {e}"));
+                }
+            }
 
-            let exit_code = exit_code_for_survivors_policy(fail_on_survivors, summary.survived);
-            let wants_ci_fail = exit_code == EXIT_SURVIVORS;
+            // Compare survivors against the known-survivors baseline (optionally rewriting it).
+            let known_summary = match (known_survivors, survivors_baseline.as_deref()) {
+                (Some(known), Some(path)) => {
+                    let known = if update_baseline {
+                        let updated = known.updated(&mutants, &discovered_fingerprints);
+                        if let Err(e) = updated.save(path) {
+                            ui.error(format!(
+                                "failed to write survivors baseline {:?}: {e}",
+                                path
+                            ));
+                            return Err(e);
+                        }
+                        ui.line(format!("updated survivors baseline: {}", path.display()));
+                        updated
+                    } else {
+                        known
+                    };
+                    Some(known.summarize(path, &mutants, &discovered_fingerprints))
+                }
+                _ => None,
+            };
+
+            let failing_survivors = known_summary
+                .as_ref()
+                .map(|k| k.new)
+                .unwrap_or(summary.survived);
+
+            let mut violations = Vec::new();
+            if exit_code_for_survivors_policy(fail_on_survivors, failing_survivors) != EXIT_OK {
+                violations.push(survivors_violation(
+                    failing_survivors,
+                    known_summary.is_some(),
+                ));
+            }
+            violations.extend(evaluate(&thresholds, &summary, &mutants));
+            let exit_code = exit_code_for_violations(&violations);
 
-            let report = MutationRunReport::success(
+            let mut report = MutationRunReport::success(
                 project_root.clone(),
                 discovered,
                 executed,
@@ -712,11 +1246,25 @@ pub fn run() -> Result<()> {
                 summary,
                 mutants,
             );
+            report.cache = cache.as_ref().map(|c| c.stats());
+            report.known_survivors = known_summary;
+            report.policy_violations = violations;
+            report.unreadable_sources = project.sources().unreadable().to_vec();
 
             // Always persist report to mutants.out/run.json
             let _ = write_run_json(&out_dir, &report);
 
-            if let Err(e) = out::write_outcomes_json(&out_dir, &report) {
+            let outcomes = match outcomes_format {
+                OutcomesFormat::ZkMutant => out::write_outcomes_json(&out_dir, &report),
+                OutcomesFormat::CargoMutants => cargo_mutants::write_outcomes_json(
+                    &out_dir,
+                    &project,
+                    &report,
+                    started_at,
+                    SystemTime::now(),
+                ),
+            };
+            if let Err(e) = outcomes {
                 ui.warn(format!("failed to write outcomes.json: {e}"));
             }
 
@@ -724,23 +1272,86 @@ pub fn run() -> Result<()> {
                 ui.warn(format!("failed to write outcome txt files: {e}"));
             }
 
-            if let Err(e) = out::write_diff_dir(&out_dir, &report.mutants) {
+            if let Err(e) =
+                out::write_diff_dir(&out_dir, &project, &report.mutants, diff_context, &ui)
+            {
                 ui.warn(format!("failed to write diff dir: {e}"));
             }
 
+            if let Err(e) = mutant_dir::write_mutant_dirs(
+                &out_dir,
+                &project,
+                &report.mutants,
+                diff_context,
+                &ui,
+            ) {
+                ui.warn(format!("failed to write mutant dirs: {e}"));
+            }
+
+            if let Err(e) = suggest::write_suggested_tests(&out_dir, &project, &report.mutants) {
+                ui.warn(format!("failed to write suggested tests: {e}"));
+            }
+
+            let sarif_paths = std::iter::once(out_dir.join("mutants.sarif")).chain(sarif);
+            for path in sarif_paths {
+                if let Err(e) = sarif::write_sarif(&path, &project, &report) {
+                    ui.warn(format!("failed to write SARIF {:?}: {e}", path));
+                }
+            }
+
+            if let Err(e) =
+                junit::write_junit(&out_dir, &project, &report, &cut_by_limit, diff_context)
+            {
+                ui.warn(format!("failed to write junit.xml: {e}"));
+            }
+
+            if let Err(e) = stryker::write_mutation_report(&out_dir, &project, &report) {
+                ui.warn(format!("failed to write mutation-report.json: {e}"));
+            }
+
+            if let Err(e) = html::write_html_report(&out_dir, &project, &report) {
+                ui.warn(format!("failed to write HTML report: {e}"));
+            }
+
+            let step_summary = std::env::var_os(markdown::STEP_SUMMARY_ENV)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from);
+            if markdown.is_some() || step_summary.is_some() {
+                let link_base = markdown::github_blob_base(&project_root);
+                let md = markdown::render_markdown(&project, &report, link_base.as_deref());
+                if let Some(path) = &markdown {
+                    if let Err(e) = markdown::write_markdown(path, &md) {
+                        ui.warn(format!("failed to write Markdown summary {:?}: {e}", path));
+                    }
+                }
+                if let Some(path) = &step_summary {
+                    if let Err(e) = markdown::append_step_summary(path, &md) {
+                        ui.warn(format!("failed to append step summary {:?}: {e}", path));
+                    }
+                }
+            }
+
             if let Err(e) = out::write_log(&out_dir, &report) {
                 ui.warn(format!("failed to write log: {e}"));
             }
 
+            if let Some(format) = annotations {
+                for line in render_annotations(&project, &report.mutants, format) {
+                    ui.line(line);
+                }
+            }
+
             if json {
                 print_json_and_exit(&report, exit_code);
             }
 
-            ui.line("--- mutation run summary ---");
-            ui.line(format!("mutants total:    {}", executed));
-            ui.line(format!("mutants killed:   {}", report.summary.killed));
-            ui.line(format!("mutants survived: {}", report.summary.survived));
-            ui.line(format!("mutants invalid:  {}", report.summary.invalid));
+            for line in run_summary_lines(&report) {
+                ui.line(line);
+            }
+
+            for line in render_score_breakdown(&report.breakdown, verbose) {
+                ui.line(line);
+            }
 
             if verbose {
                 print_all_mutants(&project, &report.mutants);
@@ -748,62 +1359,368 @@ pub fn run() -> Result<()> {
 
             print_surviving_mutants(&project, &report.mutants);
 
-            if wants_ci_fail {
-                ui.error(format!(
-                    "mutation testing failed policy: {} mutant(s) survived (--fail-on-survivors)",
-                    report.summary.survived
+            if let Some(line) = known_survivors_line(&report) {
+                ui.line(line);
+            }
+
+            for v in &report.policy_violations {
+                ui.error(format!("mutation testing failed policy: {}", v.message));
+            }
+            if exit_code != EXIT_OK {
+                std::process::exit(exit_code);
+            }
+
+            Ok(())
+        }
+
+        Command::Compare { old, new, json } => run_compare(&old, &new, json),
+
+        Command::Report {
+            from,
+            format,
+            output,
+            project,
+            outcome,
+            file,
+            verbose,
+            diff_context,
+        } => {
+            let mut report = load_run_report(&from)?;
+            // Ids of every mutant the run executed, taken before filtering: mutants.json
+            // entries missing from this set were cut by `--limit`.
+            let executed_ids: BTreeSet<u64> = report.mutants.iter().map(|m| m.id).collect();
+            let keep = |m: &Mutant| {
+                (outcome.is_empty() || outcome.contains(&m.outcome))
+                    && (file.is_empty() || file.iter().any(|f| path_is_under(&m.span.file, f)))
+            };
+            if !outcome.is_empty() || !file.is_empty() {
+                report.retain_mutants(keep);
+            }
+
+            let root = project.unwrap_or_else(|| report.project_root.clone());
+            let project = Project::from_root(root.clone()).with_context(|| {
+                format!(
+                    "failed to load project {:?} (pass --project if it moved)",
+                    root
+                )
+            })?;
+            warn_unreadable_sources(&Ui::new(false), &project);
+
+            let content = match format {
+                ReportFormat::Text => {
+                    let mut lines = Vec::new();
+                    if let Some(err) = &report.error {
+                        lines.push(format!("error: {err}"));
+                    }
+                    lines.extend(run_summary_lines(&report));
+                    lines.extend(render_score_breakdown(&report.breakdown, verbose));
+                    if verbose {
+                        lines.extend(render_all_mutants(&project, &report.mutants));
+                    }
+                    lines.extend(render_surviving_mutants(&project, &report.mutants));
+                    lines.extend(known_survivors_line(&report));
+                    for v in &report.policy_violations {
+                        lines.push(format!("mutation testing failed policy: {}", v.message));
+                    }
+                    lines.join("\n") + "\n"
+                }
+                ReportFormat::Json => {
+                    serde_json::to_string_pretty(&report).context("serialize report")? + "\n"
+                }
+                ReportFormat::Html => {
+                    let dir = output.unwrap_or_else(|| {
+                        from.parent()
+                            .map(Path::to_path_buf)
+                            .unwrap_or_else(|| PathBuf::from("."))
+                    });
+                    html::write_html_report(&dir, &project, &report)?;
+                    println!("wrote {}", dir.join("report").join("index.html").display());
+                    return Ok(());
+                }
+                ReportFormat::Markdown => {
+                    let link_base = markdown::github_blob_base(project.root());
+                    markdown::render_markdown(&project, &report, link_base.as_deref())
+                }
+                ReportFormat::Junit => {
+                    // Mutants cut by `--limit` only appear in the sibling `mutants.json`.
+                    let discovered_path = from.with_file_name("mutants.json");
+                    let mut not_run = if discovered_path.is_file() {
+                        out::read_mutants_json(&discovered_path)?
+                    } else {
+                        Vec::new()
+                    };
+                    not_run.retain(|m| !executed_ids.contains(&m.id) && keep(m));
+                    junit::render_junit(&project, &report, &not_run, diff_context)
+                }
+                ReportFormat::Sarif => {
+                    serde_json::to_string_pretty(&sarif::render_sarif(&project, &report))
+                        .context("serialize SARIF")?
+                        + "\n"
+                }
+            };
+
+            match output {
+                Some(path) => write_output(&path, &content),
+                None => {
+                    print!("{content}");
+                    Ok(())
+                }
+            }
+        }
+
+        Command::Show {
+            mutant,
+            project,
+            context,
+        } => {
+            let ui = Ui::new(false);
+            let project_root = Options::new(project).project_root;
+            if let Some(notice) = applied_mutant_notice(&project_root) {
+                ui.warn(format!("warning: {notice}"));
+            }
+            let project = Project::from_root(project_root.clone())
+                .with_context(|| format!("failed to load Noir project at {:?}", project_root))?;
+            warn_unreadable_sources(&ui, &project);
+            let mutants = discover_mutants(&project);
+            let m = find_mutant(&mutants, &mutant)?;
+
+            for line in render_mutant_in_context(&project, m, context)?.lines() {
+                ui.line(line);
+            }
+            Ok(())
+        }
+
+        Command::Apply {
+            mutant,
+            revert,
+            project,
+        } => {
+            let ui = Ui::new(false);
+            let project_root = Options::new(project).project_root;
+
+            if revert {
+                let applied = revert_applied(&project_root)?;
+                ui.line(format!(
+                    "reverted mutant #{} in {}",
+                    applied.id,
+                    applied.file.display()
                 ));
-                std::process::exit(EXIT_SURVIVORS);
+                return Ok(());
             }
 
+            let project = Project::from_root(project_root.clone())
+                .with_context(|| format!("failed to load Noir project at {:?}", project_root))?;
+            warn_unreadable_sources(&ui, &project);
+            let mutants = discover_mutants(&project);
+            let target = mutant.expect("clap requires a mutant unless --revert");
+            let m = find_mutant(&mutants, &target)?;
+
+            let description = format_mutant_with_location(&project, m);
+            apply_mutant(&project, m)?;
+            ui.line(format!("applied {description}"));
+            ui.line("undo with: zk-mutant apply --revert");
+            Ok(())
+        }
+
+        Command::Export {
+            ids,
+            outcome,
+            from,
+            project,
+            out,
+            diff_context,
+        } => {
+            let ui = Ui::new(false);
+            let report = from.as_deref().map(load_run_report).transpose()?;
+            let root = project
+                .or_else(|| report.as_ref().map(|r| r.project_root.clone()))
+                .unwrap_or_else(|| PathBuf::from("."));
+            let root = Options::new(root).project_root;
+            if let Some(notice) = applied_mutant_notice(&root) {
+                ui.warn(format!("warning: {notice}"));
+            }
+            let project = Project::from_root(root.clone())
+                .with_context(|| format!("failed to load Noir project at {:?}", root))?;
+            warn_unreadable_sources(&ui, &project);
+
+            let mutants = match report {
+                Some(r) => r.mutants,
+                None => discover_mutants(&project),
+            };
+            let selected = select_mutants(&mutants, &ids, &outcome)?;
+            if selected.is_empty() {
+                ui.line("no mutants matched; nothing exported");
+                return Ok(());
+            }
+
+            let written = write_patch_series(&out, &project, &selected, diff_context)?;
+            for path in &written {
+                ui.line(path.display());
+            }
+            ui.line(format!(
+                "wrote {} patch{} to {}",
+                written.len(),
+                if written.len() == 1 { "" } else { "es" },
+                out.display()
+            ));
             Ok(())
         }
     }
 }
 
-fn print_mutation_inventory(mutants: &[Mutant], ui: &Ui) {
-    ui.line("--- mutation inventory ---");
-    ui.line(format!("discovered mutants: {}", mutants.len()));
+/// Write rendered output to `path`, creating parent directories.
+fn write_output(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).with_context(|| format!("failed to create {:?}", parent))?;
+        }
+    }
+    fs::write(path, content).with_context(|| format!("failed to write {:?}", path))
+}
 
-    if mutants.is_empty() {
-        ui.line("no mutation opportunities found");
-        return;
+/// Headline counts of a run, as printed after `run` and by `report --format text`.
+fn run_summary_lines(report: &MutationRunReport) -> Vec<String> {
+    let mut lines = vec![
+        "--- mutation run summary ---".to_string(),
+        format!("mutants total:    {}", report.executed),
+        format!("mutants killed:   {}", report.summary.killed),
+        format!("mutants survived: {}", report.summary.survived),
+        format!("mutants invalid:  {}", report.summary.invalid),
+        format!("mutants timeout:  {}", report.summary.timeout),
+        format!(
+            "mutation score:   {} (killed / (killed + survived))",
+            score_label(report.summary.score)
+        ),
+    ];
+    if let Some(stats) = &report.cache {
+        lines.push(format!(
+            "result cache:     {} hit(s), {} miss(es)",
+            stats.hits, stats.misses
+        ));
     }
+    lines
+}
 
-    let mut by_operator: BTreeMap<String, usize> = BTreeMap::new();
-    let mut by_category: BTreeMap<String, usize> = BTreeMap::new();
-    let mut by_file: BTreeMap<String, usize> = BTreeMap::new();
+fn known_survivors_line(report: &MutationRunReport) -> Option<String> {
+    report.known_survivors.as_ref().map(|k| {
+        format!(
+            "survivors baseline: {} accepted, {} new, {} stale ({})",
+            k.accepted,
+            k.new,
+            k.stale,
+            k.path.display()
+        )
+    })
+}
 
-    for m in mutants {
-        let op = format!("{:?}/{}", m.operator.category, m.operator.name);
-        *by_operator.entry(op).or_insert(0) += 1;
+fn run_compare(old_path: &Path, new_path: &Path, json: bool) -> Result<()> {
+    let ui = Ui::new(json);
 
-        let cat = format!("{:?}", m.operator.category);
-        *by_category.entry(cat).or_insert(0) += 1;
+    let old = load_run_report(old_path)?;
+    let new = load_run_report(new_path)?;
+    let cmp = compare_runs(old_path, &old, new_path, &new);
+
+    if json {
+        let txt = serde_json::to_string_pretty(&cmp).context("serialize comparison")?;
+        println!("{txt}");
+        return Ok(());
+    }
 
-        let file = m.span.file.display().to_string();
-        *by_file.entry(file).or_insert(0) += 1;
+    ui.title("zk-mutant: compare");
+    for line in render_comparison(&cmp) {
+        ui.line(line);
     }
 
-    ui.line(format!("unique operators: {}", by_operator.len()));
+    Ok(())
+}
+
+fn print_mutation_inventory(inventory: &MutationInventory, ui: &Ui) {
+    ui.line("--- mutation inventory ---");
+    ui.line(format!("discovered mutants: {}", inventory.total));
+
+    if inventory.total == 0 {
+        ui.line("no mutation opportunities found");
+        return;
+    }
+
+    ui.line(format!("unique operators: {}", inventory.by_operator.len()));
 
     ui.line("by category:");
-    for (cat, count) in by_category {
+    for (cat, count) in &inventory.by_category {
         ui.line(format!("  {cat}: {count}"));
     }
 
     ui.line("by operator:");
-    for (op, count) in by_operator {
+    for (op, count) in &inventory.by_operator {
         ui.line(format!("  {op}: {count}"));
     }
 
-    let mut files: Vec<(String, usize)> = by_file.into_iter().collect();
-    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut files: Vec<(&String, &usize)> = inventory.by_file.iter().collect();
+    files.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
 
     ui.line("top files:");
     for (file, count) in files.into_iter().take(10) {
         ui.line(format!("  {file}: {count}"));
     }
+
+    if !inventory.by_function.is_empty() {
+        let mut functions: Vec<(&String, &usize)> = inventory.by_function.iter().collect();
+        functions.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        ui.line("top functions:");
+        for (function, count) in functions.into_iter().take(10) {
+            ui.line(format!("  {function}: {count}"));
+        }
+    }
+}
+
+/// Print functions with mutants, hot spots (no test references) first.
+fn print_function_heatmap(functions: &[FunctionScan], ui: &Ui) {
+    let mutated: Vec<&FunctionScan> = functions.iter().filter(|f| f.mutants > 0).collect();
+    if mutated.is_empty() {
+        return;
+    }
+
+    let hot_spots = mutated.iter().filter(|f| f.hot_spot).count();
+    ui.line("--- function heatmap ---");
+    ui.line(format!(
+        "hot spots (mutants, no test references): {hot_spots}"
+    ));
+
+    for f in mutated.iter().take(10) {
+        ui.line(format!(
+            "  {} {} ({}:{}) mutants: {}, code lines: {}, tests: {}",
+            if f.hot_spot { "!" } else { " " },
+            f.name,
+            f.file,
+            f.line,
+            f.mutants,
+            f.code_lines,
+            f.test_reference.label()
+        ));
+    }
+    if mutated.len() > 10 {
+        ui.line(format!("  ... {} more (see --json)", mutated.len() - 10));
+    }
+}
+
+/// Print `#[test]` functions that are likely to let mutants survive.
+fn print_weak_tests(weak_tests: &[WeakTest], ui: &Ui) {
+    if weak_tests.is_empty() {
+        return;
+    }
+
+    ui.line(format!("--- weak tests ({}) ---", weak_tests.len()));
+    for t in weak_tests {
+        let reasons: Vec<&str> = t.reasons.iter().map(|r| r.label()).collect();
+        ui.line(format!(
+            "  {} ({}:{}): {}",
+            t.name,
+            t.file,
+            t.line,
+            reasons.join(", ")
+        ));
+    }
 }
 
 /// Print a short summary based on the project overview.
@@ -843,4 +1760,26 @@ mod tests {
         assert_eq!(exit_code_for_survivors_policy(true, 1), EXIT_SURVIVORS);
         assert_eq!(exit_code_for_survivors_policy(true, 2), EXIT_SURVIVORS);
     }
+
+    #[test]
+    fn violations_exit_code_uses_lowest_code() {
+        let v = |kind| PolicyViolation {
+            kind,
+            message: String::new(),
+        };
+
+        assert_eq!(exit_code_for_violations(&[]), EXIT_OK);
+        assert_eq!(
+            exit_code_for_violations(&[v(ViolationKind::Timeout)]),
+            EXIT_UNSTABLE
+        );
+        assert_eq!(
+            exit_code_for_violations(&[v(ViolationKind::Invalid), v(ViolationKind::PathMinScore)]),
+            EXIT_SCORE
+        );
+        assert_eq!(
+            exit_code_for_violations(&[v(ViolationKind::MinScore), v(ViolationKind::Survivors)]),
+            EXIT_SURVIVORS
+        );
+    }
 }
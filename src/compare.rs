@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::report::{format_mutant_short, score_label};
+use crate::run_report::{MutationRunReport, mutation_score};
+
+/// A mutant that changed between two runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct MutantChange {
+    /// Key used to match the mutant across runs (normally its fingerprint).
+    pub fingerprint: String,
+
+    /// Outcome in the old run (`None` if the mutant is new).
+    pub before: Option<MutantOutcome>,
+
+    /// Outcome in the new run (`None` if the mutant was removed).
+    pub after: Option<MutantOutcome>,
+
+    /// The mutant as recorded in the newer run that contains it.
+    pub mutant: Mutant,
+}
+
+/// Difference between two `run.json` reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunComparison {
+    pub old: PathBuf,
+    pub new: PathBuf,
+
+    /// Mutation score of the old run in percent.
+    pub old_score: Option<f64>,
+
+    /// Mutation score of the new run in percent.
+    pub new_score: Option<f64>,
+
+    /// `new_score - old_score` in percentage points.
+    pub score_delta: Option<f64>,
+
+    /// Present in both runs; survived now but not before.
+    pub newly_surviving: Vec<MutantChange>,
+
+    /// Present in both runs; survived before and is killed now.
+    pub newly_killed: Vec<MutantChange>,
+
+    /// Only present in the new run.
+    pub added: Vec<MutantChange>,
+
+    /// Only present in the old run.
+    pub removed: Vec<MutantChange>,
+}
+
+/// Read a `run.json` written by `zk-mutant run`.
+pub fn load_run_report(path: &Path) -> Result<MutationRunReport> {
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    let report = serde_json::from_str(&text)
+        .with_context(|| format!("failed to parse run report {:?}", path))?;
+    Ok(report)
+}
+
+/// Match mutants of two runs by fingerprint and classify what changed.
+pub fn compare_runs(
+    old_path: &Path,
+    old: &MutationRunReport,
+    new_path: &Path,
+    new: &MutationRunReport,
+) -> RunComparison {
+    let old_by_key = index_by_key(&old.mutants);
+    let new_by_key = index_by_key(&new.mutants);
+
+    let mut newly_surviving = Vec::new();
+    let mut newly_killed = Vec::new();
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+
+    for (key, n) in &new_by_key {
+        match old_by_key.get(key) {
+            Some(o) => {
+                let change = MutantChange {
+                    fingerprint: key.clone(),
+                    before: Some(o.outcome.clone()),
+                    after: Some(n.outcome.clone()),
+                    mutant: (*n).clone(),
+                };
+
+                if n.outcome == MutantOutcome::Survived && o.outcome != MutantOutcome::Survived {
+                    newly_surviving.push(change);
+                } else if o.outcome == MutantOutcome::Survived && n.outcome == MutantOutcome::Killed
+                {
+                    newly_killed.push(change);
+                }
+            }
+            None => added.push(MutantChange {
+                fingerprint: key.clone(),
+                before: None,
+                after: Some(n.outcome.clone()),
+                mutant: (*n).clone(),
+            }),
+        }
+    }
+
+    for (key, o) in &old_by_key {
+        if !new_by_key.contains_key(key) {
+            removed.push(MutantChange {
+                fingerprint: key.clone(),
+                before: Some(o.outcome.clone()),
+                after: None,
+                mutant: (*o).clone(),
+            });
+        }
+    }
+
+    for list in [
+        &mut newly_surviving,
+        &mut newly_killed,
+        &mut added,
+        &mut removed,
+    ] {
+        list.sort_by_key(|c| c.mutant.id);
+    }
+
+    let old_score = mutation_score(old.summary.killed, old.summary.survived);
+    let new_score = mutation_score(new.summary.killed, new.summary.survived);
+    let score_delta = match (old_score, new_score) {
+        (Some(o), Some(n)) => Some(n - o),
+        _ => None,
+    };
+
+    RunComparison {
+        old: old_path.to_path_buf(),
+        new: new_path.to_path_buf(),
+        old_score,
+        new_score,
+        score_delta,
+        newly_surviving,
+        newly_killed,
+        added,
+        removed,
+    }
+}
+
+/// Render a comparison as human-readable lines.
+pub fn render_comparison(cmp: &RunComparison) -> Vec<String> {
+    let mut out = Vec::new();
+
+    out.push(format!("old: {}", cmp.old.display()));
+    out.push(format!("new: {}", cmp.new.display()));
+    out.push(format!(
+        "mutation score: {} -> {} ({})",
+        score_label(cmp.old_score),
+        score_label(cmp.new_score),
+        match cmp.score_delta {
+            Some(d) => format!("{d:+.2} pp"),
+            None => "n/a".to_string(),
+        }
+    ));
+
+    for (title, list) in [
+        ("newly surviving", &cmp.newly_surviving),
+        ("newly killed", &cmp.newly_killed),
+        ("added", &cmp.added),
+        ("removed", &cmp.removed),
+    ] {
+        out.push(format!("--- {title} ({}) ---", list.len()));
+        for c in list {
+            out.push(format!(
+                "{} ({} -> {})",
+                format_mutant_short(&c.mutant),
+                outcome_or_dash(c.before.as_ref()),
+                outcome_or_dash(c.after.as_ref()),
+            ));
+        }
+    }
+
+    out
+}
+
+/// Key mutants by fingerprint (falling back to location for reports without one).
+fn index_by_key(mutants: &[Mutant]) -> BTreeMap<String, &Mutant> {
+    mutants
+        .iter()
+        .map(|m| {
+            let key = if m.fingerprint.is_empty() {
+                format!(
+                    "{}:{}:{}",
+                    m.span.file.display(),
+                    m.span.start,
+                    m.operator.name
+                )
+            } else {
+                m.fingerprint.clone()
+            };
+            (key, m)
+        })
+        .collect()
+}
+
+fn outcome_or_dash(outcome: Option<&MutantOutcome>) -> String {
+    match outcome {
+        Some(o) => format!("{o:?}").to_lowercase(),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_report::{BaselineReport, RunSummary};
+    use crate::test_support;
+
+    fn mutant(id: u64, fp: &str, outcome: MutantOutcome) -> Mutant {
+        Mutant {
+            fingerprint: fp.to_string(),
+            span: test_support::span("src/main.nr", id as u32 * 10, id as u32 * 10 + 2),
+            outcome,
+            duration_ms: Some(1),
+            ..test_support::mutant(id, "src/main.nr", "eq_to_neq", "==", "!=")
+        }
+    }
+
+    fn report(mutants: Vec<Mutant>) -> MutationRunReport {
+        let mut summary = RunSummary::default();
+        for m in &mutants {
+            match m.outcome {
+                MutantOutcome::Killed => summary.killed += 1,
+                MutantOutcome::Survived => summary.survived += 1,
+                MutantOutcome::Invalid => summary.invalid += 1,
+                MutantOutcome::Timeout => summary.timeout += 1,
+                MutantOutcome::NotRun => {}
+            }
+        }
+
+        MutationRunReport::success(
+            PathBuf::from("p"),
+            mutants.len(),
+            mutants.len(),
+            BaselineReport {
+                success: true,
+                exit_code: Some(0),
+                duration_ms: 0,
+            },
+            summary,
+            mutants,
+        )
+    }
+
+    #[test]
+    fn compare_classifies_changes_by_fingerprint() {
+        // IDs shift between runs; fingerprints do not.
+        let old = report(vec![
+            mutant(1, "a", MutantOutcome::Killed),
+            mutant(2, "b", MutantOutcome::Survived),
+            mutant(3, "c", MutantOutcome::Survived),
+            mutant(4, "gone", MutantOutcome::Killed),
+        ]);
+        let new = report(vec![
+            mutant(1, "fresh", MutantOutcome::Survived),
+            mutant(2, "a", MutantOutcome::Survived),
+            mutant(3, "b", MutantOutcome::Killed),
+            mutant(4, "c", MutantOutcome::Survived),
+        ]);
+
+        let cmp = compare_runs(Path::new("old.json"), &old, Path::new("new.json"), &new);
+
+        let fps = |v: &[MutantChange]| -> Vec<String> {
+            v.iter().map(|c| c.fingerprint.clone()).collect()
+        };
+        assert_eq!(fps(&cmp.newly_surviving), vec!["a"]);
+        assert_eq!(fps(&cmp.newly_killed), vec!["b"]);
+        assert_eq!(fps(&cmp.added), vec!["fresh"]);
+        assert_eq!(fps(&cmp.removed), vec!["gone"]);
+
+        assert_eq!(cmp.old_score, Some(50.0));
+        assert_eq!(cmp.new_score, Some(25.0));
+        assert_eq!(cmp.score_delta, Some(-25.0));
+    }
+
+    #[test]
+    fn render_comparison_lists_sections() {
+        let old = report(vec![mutant(1, "a", MutantOutcome::Killed)]);
+        let new = report(vec![mutant(1, "a", MutantOutcome::Survived)]);
+        let cmp = compare_runs(Path::new("old.json"), &old, Path::new("new.json"), &new);
+
+        insta::assert_debug_snapshot!("render_comparison", render_comparison(&cmp));
+    }
+}
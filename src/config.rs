@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Name of the optional per-project configuration file (looked up in the project root).
+pub const CONFIG_FILE_NAME: &str = "zk-mutant.json";
+
+/// Project configuration read from `zk-mutant.json`.
+///
+/// Example:
+///
+/// ```json
+/// {
+///   "thresholds": {
+///     "min_score": 70,
+///     "paths": { "src/merkle/": 85 },
+///     "fail_on_timeout": true
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub thresholds: ThresholdConfig,
+}
+
+/// CI thresholds; CLI flags take precedence over these values.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdConfig {
+    /// Minimum overall mutation score in percent.
+    #[serde(default)]
+    pub min_score: Option<f64>,
+
+    /// Minimum mutation score per path prefix (relative to the project root).
+    #[serde(default)]
+    pub paths: BTreeMap<String, f64>,
+
+    /// Fail the run if any mutant is invalid.
+    #[serde(default)]
+    pub fail_on_invalid: bool,
+
+    /// Fail the run if any mutant times out.
+    #[serde(default)]
+    pub fail_on_timeout: bool,
+}
+
+impl Config {
+    /// Load configuration.
+    ///
+    /// An explicit `path` must exist. Without one, `<project_root>/zk-mutant.json` is used
+    /// when present and defaults apply otherwise.
+    pub fn load(project_root: &Path, path: Option<&Path>) -> Result<Self> {
+        let path: PathBuf = match path {
+            Some(p) => p.to_path_buf(),
+            None => {
+                let default = project_root.join(CONFIG_FILE_NAME);
+                if !default.exists() {
+                    return Ok(Self::default());
+                }
+                default
+            }
+        };
+
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("failed to read {:?}", path))?;
+        let config: Config = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse config {:?}", path))?;
+
+        for (prefix, score) in &config.thresholds.paths {
+            check_percentage(*score)
+                .with_context(|| format!("invalid threshold for {prefix:?} in {:?}", path))?;
+        }
+        if let Some(score) = config.thresholds.min_score {
+            check_percentage(score).with_context(|| format!("invalid min_score in {:?}", path))?;
+        }
+
+        Ok(config)
+    }
+}
+
+/// Ensure a score threshold is a percentage in `0..=100`.
+pub fn check_percentage(value: f64) -> Result<()> {
+    if !(0.0..=100.0).contains(&value) {
+        anyhow::bail!("score threshold must be between 0 and 100, got {value}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn missing_default_config_uses_defaults() {
+        let td = TempDir::new().unwrap();
+        let config = Config::load(td.path(), None).unwrap();
+
+        assert_eq!(config.thresholds.min_score, None);
+        assert!(config.thresholds.paths.is_empty());
+        assert!(!config.thresholds.fail_on_invalid);
+    }
+
+    #[test]
+    fn loads_thresholds_from_project_root() {
+        let td = TempDir::new().unwrap();
+        fs::write(
+            td.path().join(CONFIG_FILE_NAME),
+            r#"{ "thresholds": { "min_score": 70, "paths": { "src/merkle/": 85 }, "fail_on_timeout": true } }"#,
+        )
+        .unwrap();
+
+        let config = Config::load(td.path(), None).unwrap();
+        assert_eq!(config.thresholds.min_score, Some(70.0));
+        assert_eq!(config.thresholds.paths.get("src/merkle/"), Some(&85.0));
+        assert!(config.thresholds.fail_on_timeout);
+        assert!(!config.thresholds.fail_on_invalid);
+    }
+
+    #[test]
+    fn rejects_out_of_range_thresholds_and_missing_explicit_file() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().join("custom.json");
+        fs::write(&path, r#"{ "thresholds": { "paths": { "src/": 120 } } }"#).unwrap();
+
+        assert!(Config::load(td.path(), Some(&path)).is_err());
+        assert!(Config::load(td.path(), Some(&td.path().join("missing.json"))).is_err());
+    }
+}
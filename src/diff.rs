@@ -0,0 +1,168 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::mutant::Mutant;
+use crate::patch::apply_checked_patch;
+use crate::project::Project;
+
+/// Default number of unchanged lines shown around a change.
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Render a git-style unified diff between two versions of `path`.
+///
+/// The diff has a single hunk covering every changed line (mutants change one span),
+/// uses `a/` and `b/` path prefixes and can be applied with `git apply` or `patch -p1`.
+/// Returns an empty string when the texts are identical.
+pub fn unified_diff(path: &Path, original: &str, mutated: &str, context: usize) -> String {
+    let old: Vec<&str> = original.split_inclusive('\n').collect();
+    let new: Vec<&str> = mutated.split_inclusive('\n').collect();
+
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix == old.len() && prefix == new.len() {
+        return String::new();
+    }
+
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_changed_end = old.len() - suffix;
+    let new_changed_end = new.len() - suffix;
+
+    let hunk_start = prefix.saturating_sub(context);
+    let trailing = suffix.min(context);
+    let old_len = old_changed_end + trailing - hunk_start;
+    let new_len = new_changed_end + trailing - hunk_start;
+
+    let file = path.to_string_lossy().replace('\\', "/");
+
+    let mut out = String::new();
+    out.push_str(&format!("diff --git a/{file} b/{file}\n"));
+    out.push_str(&format!("--- a/{file}\n"));
+    out.push_str(&format!("+++ b/{file}\n"));
+    out.push_str(&format!(
+        "@@ -{} +{} @@\n",
+        hunk_range(hunk_start, old_len),
+        hunk_range(hunk_start, new_len)
+    ));
+
+    for line in &old[hunk_start..prefix] {
+        push_line(&mut out, ' ', line);
+    }
+    for line in &old[prefix..old_changed_end] {
+        push_line(&mut out, '-', line);
+    }
+    for line in &new[prefix..new_changed_end] {
+        push_line(&mut out, '+', line);
+    }
+    for line in &old[old_changed_end..old_changed_end + trailing] {
+        push_line(&mut out, ' ', line);
+    }
+
+    out
+}
+
+/// Unified diff of the mutant's file before and after applying the mutation.
+pub fn mutant_diff(project: &Project, mutant: &Mutant, context: usize) -> Result<String> {
+    let text = project
+        .sources()
+        .get(&mutant.span.file)
+        .ok_or_else(|| anyhow::anyhow!("source file {:?} not part of project", mutant.span.file))?;
+
+    let start = mutant.span.start as usize;
+    let end = mutant.span.end as usize;
+    if !text.matches(start, end, &mutant.original_snippet) {
+        anyhow::bail!(
+            "mutant {} no longer matches {:?} (source changed since discovery?)",
+            mutant.id,
+            mutant.span.file
+        );
+    }
+
+    let original = text.code();
+    let mutated = apply_checked_patch(
+        original,
+        &mutant.span,
+        &mutant.original_snippet,
+        &mutant.mutated_snippet,
+    );
+
+    Ok(unified_diff(&mutant.span.file, original, &mutated, context))
+}
+
+/// Hunk range in `start,len` form (1-based; `len` omitted when it is 1).
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+fn push_line(out: &mut String, marker: char, line: &str) {
+    out.push(marker);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+    use std::path::PathBuf;
+
+    #[test]
+    fn unified_diff_includes_context_and_line_numbers() {
+        let original = "a\nb\nc\nd\nif x == y {\ne\nf\ng\nh\n";
+        let mutated = original.replace("==", "!=");
+
+        insta::assert_snapshot!(
+            "unified_diff_context",
+            unified_diff(Path::new("src/main.nr"), original, &mutated, 3)
+        );
+    }
+
+    #[test]
+    fn unified_diff_clamps_context_at_file_edges() {
+        let diff = unified_diff(Path::new("src/x.nr"), "x == y\nz\n", "x != y\nz\n", 3);
+        assert!(diff.contains("@@ -1,2 +1,2 @@\n-x == y\n+x != y\n z\n"));
+
+        let diff = unified_diff(Path::new("src/x.nr"), "a\nx == y", "a\nx != y", 0);
+        assert!(diff.contains(
+            "@@ -2 +2 @@\n-x == y\n\\ No newline at end of file\n+x != y\n\\ No newline at end of file\n"
+        ));
+    }
+
+    #[test]
+    fn unified_diff_of_identical_texts_is_empty() {
+        assert_eq!(unified_diff(Path::new("a.nr"), "x\n", "x\n", 3), "");
+    }
+
+    #[test]
+    fn mutant_diff_uses_project_relative_paths() {
+        let project = Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load");
+        let mutants = discover_mutants(&project);
+
+        let diff = mutant_diff(&project, &mutants[0], 1).unwrap();
+        let file = mutants[0].span.file.to_string_lossy().replace('\\', "/");
+
+        assert!(diff.starts_with(&format!("diff --git a/{file} b/{file}\n")));
+        assert!(diff.lines().any(|l| l.starts_with('+')
+            && !l.starts_with("+++")
+            && l.contains(&mutants[0].mutated_snippet)));
+        assert_eq!(diff.lines().filter(|l| l.starts_with("@@")).count(), 1);
+    }
+}
@@ -1,25 +1,27 @@
+use std::collections::BTreeMap;
 use std::ops::Range;
 use std::path::Path;
 
+use crate::hash::StableHasher;
+use crate::line_index::LineIndex;
 use crate::mutant::{Mutant, MutantOutcome, MutationOperator, OperatorCategory};
 use crate::project::Project;
+use crate::source_map::SourceText;
 use crate::span::SourceSpan;
+use crate::syntax::{
+    FunctionItem, ScopeItem, ScopeKind, enclosing_function, enclosing_scopes, find_functions,
+    find_scopes, is_test_function, outer_attributes, token_index_at, tokenize,
+};
+
+/// Number of tokens on each side of a mutated operator that feed its fingerprint.
+const FINGERPRINT_CONTEXT_TOKENS: usize = 2;
 
 /// Discover comparison-operator mutants in all source files of a project.
 pub fn discover_mutants(project: &Project) -> Vec<Mutant> {
     let mut mutants = Vec::new();
 
-    for src in project.source_files() {
-        let path = src.relative_path().to_path_buf();
-        let code = match src.read_to_string() {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("failed to read source file {:?}: {e}", src.path());
-                continue;
-            }
-        };
-
-        mutants.extend(discover_mutants_in_code(&path, &code));
+    for (path, text) in project.sources().iter() {
+        mutants.extend(discover_mutants_in_code(path, text));
     }
 
     // 1) Sort by file, then by start offset
@@ -38,7 +40,8 @@ pub fn discover_mutants(project: &Project) -> Vec<Mutant> {
 }
 
 /// Discover mutants in a single file's code (project-relative `path`).
-fn discover_mutants_in_code(path: &Path, code: &str) -> Vec<Mutant> {
+fn discover_mutants_in_code(path: &Path, text: &SourceText) -> Vec<Mutant> {
+    let code = text.code();
     let mut mutants = Vec::new();
 
     // Compute byte ranges that belong to #[test] functions in this file.
@@ -86,16 +89,23 @@ fn discover_mutants_in_code(path: &Path, code: &str) -> Vec<Mutant> {
             };
 
             let mutant = Mutant {
-                id: 0, // placeholder, will be overwritten after sorting
+                id: 0,                      // placeholder, will be overwritten after sorting
+                fingerprint: String::new(), // filled in by `annotate_sites`
                 operator: MutationOperator {
                     category: category.clone(),
                     name: op_name.to_string(),
                 },
                 span,
+                function: None,       // filled in by `annotate_sites`
+                module_path: None,    // filled in by `annotate_sites`
+                impl_context: None,   // filled in by `annotate_sites`
+                qualified_name: None, // filled in by `annotate_sites`
+                location: None,       // filled in by `annotate_sites`
                 original_snippet: pattern.to_string(),
                 mutated_snippet: replacement.to_string(),
                 outcome: MutantOutcome::NotRun,
                 duration_ms: None,
+                killed_by: Vec::new(),
             };
 
             mutants.push(mutant);
@@ -103,9 +113,124 @@ fn discover_mutants_in_code(path: &Path, code: &str) -> Vec<Mutant> {
         }
     }
 
+    mutants.sort_by_key(|m| m.span.start);
+    annotate_sites(path, &text.index(), &mut mutants);
+
     mutants
 }
 
+/// Record the enclosing function, module path, `impl`/`trait` context and line/column
+/// location, and assign content-based fingerprints to mutants of a single file (sorted
+/// by offset).
+///
+/// A fingerprint hashes the file path, the qualified name of the enclosing function (so
+/// same-named methods of different `impl` blocks differ), the operator, the
+/// whitespace-normalized tokens around the mutated operator and an occurrence index
+/// that disambiguates otherwise identical sites. Unlike IDs, it does not change when
+/// unrelated code is added above the mutant.
+fn annotate_sites(path: &Path, lines: &LineIndex, mutants: &mut [Mutant]) {
+    let code = lines.code();
+    let tokens = tokenize(code);
+    let functions = find_functions(code, &tokens);
+    let scopes = find_scopes(code, &tokens);
+    let file = path.to_string_lossy().replace('\\', "/");
+    let file_module = file_module_path(path);
+
+    let mut occurrences: BTreeMap<String, u64> = BTreeMap::new();
+
+    for m in mutants.iter_mut() {
+        let start = m.span.start as usize;
+        let function = enclosing_function(&functions, start)
+            .map(|f| f.name.as_str())
+            .unwrap_or("");
+
+        let (modules, owner) = scope_path(&file_module, &scopes, start);
+        m.function = (!function.is_empty()).then(|| function.to_string());
+        m.location = lines.locate(start, m.span.end as usize, &m.mutated_snippet);
+        m.module_path = (!modules.is_empty()).then(|| modules.join("::"));
+        m.impl_context = owner.map(|s| s.header.clone());
+        m.qualified_name = (!function.is_empty()).then(|| qualify(&modules, owner, function));
+
+        let idx = token_index_at(&tokens, start);
+        let lo = idx.saturating_sub(FINGERPRINT_CONTEXT_TOKENS);
+        let hi = (idx + 1 + FINGERPRINT_CONTEXT_TOKENS).min(tokens.len());
+        let context: Vec<&str> = tokens[lo..hi].iter().map(|t| t.text(code)).collect();
+
+        let mut site = StableHasher::new();
+        site.write_str(&file);
+        site.write_str(m.qualified_name.as_deref().unwrap_or(""));
+        site.write_str(&m.operator.name);
+        site.write_str(&context.join(" "));
+        let site = site.finish_hex();
+
+        let occurrence = occurrences.entry(site.clone()).or_insert(0);
+
+        let mut h = StableHasher::new();
+        h.write_str(&site);
+        h.write_str(&occurrence.to_string());
+        m.fingerprint = h.finish_hex();
+
+        *occurrence += 1;
+    }
+}
+
+/// Qualified name of a function in the file at `path`, as recorded on its mutants (for
+/// example `utils::Point::eq`).
+pub(crate) fn qualified_function_name(
+    path: &Path,
+    scopes: &[ScopeItem],
+    function: &FunctionItem,
+) -> String {
+    let (modules, owner) = scope_path(&file_module_path(path), scopes, function.start);
+    qualify(&modules, owner, &function.name)
+}
+
+/// Module path at `offset` and the innermost `impl`/`trait` block around it.
+fn scope_path<'s>(
+    file_module: &[String],
+    scopes: &'s [ScopeItem],
+    offset: usize,
+) -> (Vec<String>, Option<&'s ScopeItem>) {
+    let mut modules = file_module.to_vec();
+    let mut owner = None;
+    for scope in enclosing_scopes(scopes, offset) {
+        match scope.kind {
+            ScopeKind::Mod => modules.push(scope.name.clone()),
+            ScopeKind::Impl | ScopeKind::Trait => owner = Some(scope),
+        }
+    }
+    (modules, owner)
+}
+
+/// `<modules>::<owner>::<function>`, skipping empty parts.
+fn qualify(modules: &[String], owner: Option<&ScopeItem>, function: &str) -> String {
+    let mut parts = modules.to_vec();
+    parts.extend(owner.map(|s| s.name.clone()));
+    parts.push(function.to_string());
+    parts.join("::")
+}
+
+/// Module path implied by a project-relative file path, following Noir's layout:
+/// `src/main.nr` and `src/lib.nr` are the crate root, `src/foo.nr` and `src/foo/mod.nr`
+/// are `foo`, `src/foo/bar.nr` is `foo::bar`.
+fn file_module_path(path: &Path) -> Vec<String> {
+    let mut parts: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if parts.first().is_some_and(|p| p == "src") {
+        parts.remove(0);
+    }
+    if let Some(last) = parts.pop() {
+        let stem = last.strip_suffix(".nr").unwrap_or(&last).to_string();
+        let is_root = parts.is_empty() && (stem == "main" || stem == "lib");
+        if stem != "mod" && !is_root {
+            parts.push(stem);
+        }
+    }
+    parts
+}
+
 fn should_skip_overlapping_single_char(pattern: &str, bytes: &[u8], start: usize) -> bool {
     if pattern.len() != 1 {
         return false;
@@ -141,62 +266,17 @@ fn comparison_mutation_rules()
     ]
 }
 
-/// Return byte ranges corresponding to the bodies of `#[test]` functions.
+/// Return byte ranges of `#[test]` functions, from the `fn` keyword to the end of the body.
 ///
-/// This is a simple textual heuristic similar to noir-metrics: it looks for
-/// a `#[test...]` attribute followed by `fn ...`, then tracks `{` / `}`
-/// brace depth to find the end of that function body.
-fn find_test_code_ranges(code: &str) -> Vec<Range<usize>> {
-    let mut ranges = Vec::new();
-
-    let mut pending_test_attr = false;
-    let mut inside_test = false;
-    let mut brace_depth: i32 = 0;
-
-    let mut offset: usize = 0;
-    let mut test_start: Option<usize> = None;
-
-    for line in code.lines() {
-        let line_len = line.len();
-        let line_start = offset;
-        let line_end = offset + line_len;
-
-        let trimmed = line.trim_start();
-
-        if trimmed.starts_with("#[test") {
-            pending_test_attr = true;
-        } else if (trimmed.starts_with("fn ") || trimmed.starts_with("pub fn "))
-            && pending_test_attr
-        {
-            pending_test_attr = false;
-            inside_test = true;
-            test_start = Some(line_start);
-            brace_depth = 0;
-        }
-
-        // Track braces on this line.
-        for ch in line.chars() {
-            match ch {
-                '{' => brace_depth += 1,
-                '}' => brace_depth -= 1,
-                _ => {}
-            }
-        }
-
-        if inside_test && brace_depth == 0 {
-            // End of test function body.
-            let end = line_end + 1; // include trailing newline
-            if let Some(start) = test_start.take() {
-                ranges.push(start..end);
-            }
-            inside_test = false;
-        }
-
-        // Move offset past this line and its newline.
-        offset = line_end + 1;
-    }
-
-    ranges
+/// A function is a test when one of its outer attributes is `test` or `test(...)` (see
+/// [`is_test_function`]); `scan` classifies functions the same way.
+pub(crate) fn find_test_code_ranges(code: &str) -> Vec<Range<usize>> {
+    let tokens = tokenize(code);
+    find_functions(code, &tokens)
+        .into_iter()
+        .filter(|f| is_test_function(&outer_attributes(code, &tokens, f.start)))
+        .map(|f| f.start..f.body.end)
+        .collect()
 }
 
 /// Return byte ranges corresponding to line (`// ...`) and block (`/* ... */`) comments.
@@ -377,7 +457,7 @@ fn a(x: u32, y: u32) {
 "#;
 
         let path = PathBuf::from("src/main.nr");
-        let mutants = discover_mutants_in_code(&path, code);
+        let mutants = discover_mutants_in_code(&path, &SourceText::new(code.to_string()));
 
         // One mutant per operator occurrence: <=, >=, <, >
         assert_eq!(mutants.len(), 4);
@@ -443,6 +523,33 @@ fn helper() {
         );
     }
 
+    #[test]
+    fn test_ranges_follow_test_attributes() {
+        let code = r#"
+#[test]
+unconstrained fn via_modifier() {
+    assert(1 == 1);
+}
+
+#[test(should_fail)]
+pub(crate) fn via_visibility() {
+    assert(2 == 3);
+}
+
+// #[test]
+fn helper(x: u32) {
+    assert(x == 4);
+}
+"#;
+
+        let mutants = discover_mutants_in_code(
+            &PathBuf::from("src/main.nr"),
+            &SourceText::new(code.to_string()),
+        );
+        let functions: Vec<Option<&str>> = mutants.iter().map(|m| m.function.as_deref()).collect();
+        assert_eq!(functions, vec![Some("helper")]);
+    }
+
     #[test]
     fn in_any_range_is_start_inclusive_end_exclusive() {
         let code = r#"
@@ -470,6 +577,117 @@ fn t() {
         assert_eq!(advance_search_start(100, 100, 100), 100);
     }
 
+    #[test]
+    fn fingerprints_survive_unrelated_edits_above() {
+        let code = r#"
+fn check(a: Field, b: Field) {
+    assert(a == b);
+    assert(a == b);
+}
+"#;
+        let edited = format!("fn added() {{\n    assert(1 != 2);\n}}\n{code}");
+
+        let path = PathBuf::from("src/main.nr");
+        let before = discover_mutants_in_code(&path, &SourceText::new(code.to_string()));
+        let after = discover_mutants_in_code(&path, &SourceText::new(edited.clone()));
+
+        assert_eq!(before.len(), 2);
+        assert_ne!(
+            before[0].fingerprint, before[1].fingerprint,
+            "identical sites must be disambiguated by occurrence index"
+        );
+
+        let after_eq: Vec<&str> = after
+            .iter()
+            .filter(|m| m.operator.name == "eq_to_neq")
+            .map(|m| m.fingerprint.as_str())
+            .collect();
+        let before_fps: Vec<&str> = before.iter().map(|m| m.fingerprint.as_str()).collect();
+        assert_eq!(after_eq, before_fps);
+    }
+
+    #[test]
+    fn mutants_record_module_path_and_impl_context() {
+        let code = r#"
+fn top(x: u32) { assert(x == 1); }
+
+mod inner {
+    impl Point {
+        fn is_origin(self) { assert(self.x == 0); }
+    }
+
+    impl Eq for Point {
+        fn check(self, other: Point) { assert(self.x == other.x); }
+    }
+}
+"#;
+
+        let describe = |path: &str| -> Vec<(Option<String>, Option<String>, Option<String>)> {
+            discover_mutants_in_code(&PathBuf::from(path), &SourceText::new(code.to_string()))
+                .into_iter()
+                .map(|m| (m.module_path, m.impl_context, m.qualified_name))
+                .collect()
+        };
+        let s = |v: &str| Some(v.to_string());
+
+        assert_eq!(
+            describe("src/geometry/point.nr"),
+            vec![
+                (s("geometry::point"), None, s("geometry::point::top")),
+                (
+                    s("geometry::point::inner"),
+                    s("impl Point"),
+                    s("geometry::point::inner::Point::is_origin")
+                ),
+                (
+                    s("geometry::point::inner"),
+                    s("impl Eq for Point"),
+                    s("geometry::point::inner::Point::check")
+                ),
+            ]
+        );
+        assert_eq!(describe("src/main.nr")[0], (None, None, s("top")));
+    }
+
+    #[test]
+    fn file_module_path_follows_noir_layout() {
+        let path = |p: &str| file_module_path(Path::new(p)).join("::");
+        assert_eq!(path("src/main.nr"), "");
+        assert_eq!(path("src/lib.nr"), "");
+        assert_eq!(path("src/utils.nr"), "utils");
+        assert_eq!(path("src/foo/mod.nr"), "foo");
+        assert_eq!(path("src/foo/bar.nr"), "foo::bar");
+    }
+
+    #[test]
+    fn fingerprints_depend_on_enclosing_function() {
+        let a = "fn f(x: u32) { assert(x == 1); }";
+        let b = "fn g(x: u32) { assert(x == 1); }";
+
+        let path = PathBuf::from("src/main.nr");
+        let fa = &discover_mutants_in_code(&path, &SourceText::new(a.to_string()))[0].fingerprint;
+        let fb = &discover_mutants_in_code(&path, &SourceText::new(b.to_string()))[0].fingerprint;
+
+        assert_ne!(fa, fb);
+    }
+
+    #[test]
+    fn fingerprints_distinguish_same_named_methods_in_different_impls() {
+        let code = r#"
+impl A { fn check(x: u32) { assert(x == 1); } }
+impl B { fn check(x: u32) { assert(x == 1); } }
+"#;
+
+        let mutants = discover_mutants_in_code(
+            &PathBuf::from("src/main.nr"),
+            &SourceText::new(code.to_string()),
+        );
+        let fps: Vec<&str> = mutants.iter().map(|m| m.fingerprint.as_str()).collect();
+
+        assert_eq!(fps.len(), 2);
+        assert_ne!(fps[0], fps[1]);
+    }
+
     #[test]
     fn discover_ignores_line_and_block_comments() {
         let code = r#"
@@ -488,7 +706,7 @@ fn helper() {
 "#;
 
         let path = PathBuf::from("src/main.nr");
-        let mutants = discover_mutants_in_code(&path, code);
+        let mutants = discover_mutants_in_code(&path, &SourceText::new(code.to_string()));
 
         // Only the two operators in real code should be mutated.
         assert_eq!(
@@ -0,0 +1,206 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::diff::mutant_diff;
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::report::{format_mutant_with_location, outcome_label};
+
+/// Placeholder commit id used in the `From <sha>` line, as `git format-patch` does for
+/// patches that are not tied to a commit.
+const NULL_COMMIT: &str = "0000000000000000000000000000000000000000";
+
+/// Fixed author and date so exported patches are reproducible.
+const PATCH_AUTHOR: &str = "zk-mutant <zk-mutant@localhost>";
+const PATCH_DATE: &str = "Thu, 1 Jan 1970 00:00:00 +0000";
+
+/// Subject line for a mutant's patch, for example `Mutant #12 src/utils.nr:4 eq_to_neq`.
+pub fn patch_subject(project: &Project, m: &Mutant) -> String {
+    let file = m.span.file.to_string_lossy().replace('\\', "/");
+    let line = project
+        .sources()
+        .line_col(&m.span.file, m.span.start as usize)
+        .map(|(line, _)| format!(":{line}"))
+        .unwrap_or_default();
+    format!("Mutant #{} {file}{line} {}", m.id, m.operator.name)
+}
+
+/// Render one mutant as a `git format-patch` style mail that `git am` and `git apply`
+/// accept (paths are relative to the project root).
+pub fn render_format_patch(project: &Project, m: &Mutant, diff_context: usize) -> Result<String> {
+    let diff = mutant_diff(project, m, diff_context)?;
+    let file = m.span.file.to_string_lossy().replace('\\', "/");
+    let (insertions, deletions) = diff_counts(&diff);
+
+    let mut out = String::new();
+    out.push_str(&format!("From {NULL_COMMIT} Mon Sep 17 00:00:00 2001\n"));
+    out.push_str(&format!("From: {PATCH_AUTHOR}\n"));
+    out.push_str(&format!("Date: {PATCH_DATE}\n"));
+    out.push_str(&format!(
+        "Subject: [PATCH] {}\n\n",
+        patch_subject(project, m)
+    ));
+
+    out.push_str(&format!("{}\n\n", format_mutant_with_location(project, m)));
+    if let Some(f) = m.function_label() {
+        out.push_str(&format!("Function: {f}\n"));
+    }
+    if let Some(ctx) = &m.impl_context {
+        out.push_str(&format!("Context: {ctx}\n"));
+    }
+    out.push_str(&format!("Outcome: {}\n", outcome_label(&m.outcome)));
+    if !m.fingerprint.is_empty() {
+        out.push_str(&format!("Fingerprint: {}\n", m.fingerprint));
+    }
+
+    out.push_str("---\n");
+    out.push_str(&format!(
+        " {file} | {} {}{}\n",
+        insertions + deletions,
+        "+".repeat(insertions),
+        "-".repeat(deletions)
+    ));
+    out.push_str(&format!(
+        " 1 file changed, {insertions} insertion{}(+), {deletions} deletion{}(-)\n\n",
+        if insertions == 1 { "" } else { "s" },
+        if deletions == 1 { "" } else { "s" }
+    ));
+    out.push_str(&diff);
+    out.push_str(&format!("-- \nzk-mutant {}\n\n", env!("CARGO_PKG_VERSION")));
+
+    Ok(out)
+}
+
+/// Write one `NNNN-<subject>.patch` per mutant (in id order) to `out_dir`.
+///
+/// Returns the written paths. Mutants whose source no longer matches are an error, so a
+/// stale `run.json` cannot silently produce patches against the wrong lines.
+pub fn write_patch_series(
+    out_dir: &Path,
+    project: &Project,
+    mutants: &[&Mutant],
+    diff_context: usize,
+) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir).with_context(|| format!("failed to create {:?}", out_dir))?;
+
+    let mut ordered = mutants.to_vec();
+    ordered.sort_by_key(|m| m.id);
+
+    let mut written = Vec::new();
+    for (i, m) in ordered.into_iter().enumerate() {
+        let patch = render_format_patch(project, m, diff_context)?;
+        let name = format!(
+            "{:04}-{}.patch",
+            i + 1,
+            patch_file_slug(&patch_subject(project, m))
+        );
+        let path = out_dir.join(name);
+        fs::write(&path, patch).with_context(|| format!("failed to write {:?}", path))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Keep only the mutants a user asked for: all when no ids and outcomes are given.
+pub fn select_mutants<'a>(
+    mutants: &'a [Mutant],
+    ids: &[u64],
+    outcomes: &[MutantOutcome],
+) -> Result<Vec<&'a Mutant>> {
+    for id in ids {
+        if !mutants.iter().any(|m| m.id == *id) {
+            anyhow::bail!("no mutant with id {id} ({} mutants known)", mutants.len());
+        }
+    }
+
+    Ok(mutants
+        .iter()
+        .filter(|m| ids.is_empty() || ids.contains(&m.id))
+        .filter(|m| outcomes.is_empty() || outcomes.contains(&m.outcome))
+        .collect())
+}
+
+/// File-name slug in the spirit of `git format-patch` (runs of other characters become `-`).
+fn patch_file_slug(subject: &str) -> String {
+    let mut out = String::new();
+    for c in subject.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            out.push(c);
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_matches(['-', '.']).to_string()
+}
+
+/// Added and removed lines in a unified diff (file headers excluded).
+fn diff_counts(diff: &str) -> (usize, usize) {
+    let mut insertions = 0;
+    let mut deletions = 0;
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if line.starts_with('+') {
+            insertions += 1;
+        } else if line.starts_with('-') {
+            deletions += 1;
+        }
+    }
+    (insertions, deletions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_report;
+
+    #[test]
+    fn render_format_patch_snapshot_fixture() {
+        let (project, mut report) = fixture_report(&[
+            MutantOutcome::NotRun,
+            MutantOutcome::NotRun,
+            MutantOutcome::Survived,
+        ]);
+        report.mutants[2].fingerprint = "<fingerprint>".to_string();
+
+        let patch = render_format_patch(&project, &report.mutants[2], 1).unwrap();
+        insta::assert_snapshot!(
+            "format_patch",
+            patch.replace(env!("CARGO_PKG_VERSION"), "<version>")
+        );
+    }
+
+    #[test]
+    fn patch_file_slug_matches_format_patch_style() {
+        assert_eq!(
+            patch_file_slug("Mutant #12 src/utils.nr:4 eq_to_neq"),
+            "Mutant-12-src-utils.nr-4-eq_to_neq"
+        );
+    }
+
+    #[test]
+    fn select_mutants_filters_by_id_and_outcome() {
+        let (_, report) = fixture_report(&[
+            MutantOutcome::NotRun,
+            MutantOutcome::Survived,
+            MutantOutcome::NotRun,
+            MutantOutcome::Survived,
+        ]);
+        let mutants = report.mutants;
+
+        let ids = |v: Vec<&Mutant>| v.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(select_mutants(&mutants, &[], &[]).unwrap()),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(ids(select_mutants(&mutants, &[4, 1], &[]).unwrap()), [1, 4]);
+        assert_eq!(
+            ids(select_mutants(&mutants, &[1, 2], &[MutantOutcome::Survived]).unwrap()),
+            [2]
+        );
+        assert!(select_mutants(&mutants, &[9], &[]).is_err());
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::report::{duration_label, outcome_label, score_label};
+use crate::run_report::{MutationRunReport, ScoreBucket};
+use crate::source_map::SourceText;
+use crate::syntax::{TokenKind, tokenize};
+
+/// Noir keywords highlighted in source views.
+const KEYWORDS: &[&str] = &[
+    "as",
+    "assert",
+    "assert_eq",
+    "break",
+    "comptime",
+    "constrain",
+    "continue",
+    "crate",
+    "dep",
+    "else",
+    "false",
+    "fn",
+    "for",
+    "global",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "pub",
+    "return",
+    "self",
+    "Self",
+    "struct",
+    "trait",
+    "true",
+    "type",
+    "unconstrained",
+    "unsafe",
+    "use",
+    "where",
+    "while",
+];
+
+/// Built-in types highlighted in source views.
+const TYPES: &[&str] = &[
+    "Field", "bool", "str", "u1", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64",
+];
+
+/// Inline stylesheet shared by all pages (no external assets).
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #1f2328; }
+h1 { font-size: 1.4em; } h2 { font-size: 1.1em; margin-top: 2em; }
+a { color: #0969da; }
+table.summary { border-collapse: collapse; }
+table.summary th, table.summary td { border: 1px solid #d0d7de; padding: 4px 10px; text-align: left; }
+table.summary td.num { text-align: right; font-variant-numeric: tabular-nums; }
+table.source { border-collapse: collapse; font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 13px; }
+table.source td { padding: 0 8px; white-space: pre; vertical-align: top; }
+table.source td.ln { color: #8c959f; text-align: right; user-select: none; border-right: 1px solid #d0d7de; }
+.kw { color: #cf222e; } .ty { color: #8250df; } .num { color: #0550ae; } .str { color: #0a3069; } .com { color: #6e7781; font-style: italic; }
+.mut { border-bottom: 3px solid; cursor: help; }
+.mut.killed { background: #dafbe1; border-color: #1a7f37; }
+.mut.survived { background: #ffebe9; border-color: #cf222e; }
+.mut.timeout { background: #fff8c5; border-color: #9a6700; }
+.mut.invalid, .mut.not_run { background: #eaeef2; border-color: #8c959f; }
+.outcome.killed { color: #1a7f37; } .outcome.survived { color: #cf222e; font-weight: bold; }
+.outcome.timeout { color: #9a6700; } .outcome.invalid, .outcome.not_run { color: #6e7781; }
+"#;
+
+/// Syntax class of a byte in a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Plain,
+    Keyword,
+    Type,
+    Number,
+    Str,
+    Comment,
+}
+
+impl Class {
+    fn css(self) -> Option<&'static str> {
+        match self {
+            Class::Plain => None,
+            Class::Keyword => Some("kw"),
+            Class::Type => Some("ty"),
+            Class::Number => Some("num"),
+            Class::Str => Some("str"),
+            Class::Comment => Some("com"),
+        }
+    }
+}
+
+/// Write `report/index.html` plus one annotated page per mutated file.
+///
+/// Pages mirror the source layout (`report/src/main.nr.html`) and only use inline CSS,
+/// so the directory can be archived and opened without network access.
+pub fn write_html_report(
+    out_dir: &Path,
+    project: &Project,
+    report: &MutationRunReport,
+) -> Result<()> {
+    let report_dir = out_dir.join("report");
+    fs::create_dir_all(&report_dir)
+        .with_context(|| format!("failed to create {:?}", report_dir))?;
+
+    let mut by_file: BTreeMap<PathBuf, Vec<&Mutant>> = BTreeMap::new();
+    for m in &report.mutants {
+        by_file.entry(m.span.file.clone()).or_default().push(m);
+    }
+
+    for (file, mutants) in &by_file {
+        let Some(text) = project.sources().get(file) else {
+            continue;
+        };
+        let bucket = report
+            .breakdown
+            .by_file
+            .iter()
+            .find(|b| Path::new(&b.key) == file.as_path());
+
+        let html = render_file_page(file, text, mutants, bucket);
+        let path = report_dir.join(page_path(file));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("failed to create {:?}", parent))?;
+        }
+        fs::write(&path, html).with_context(|| format!("failed to write {:?}", path))?;
+    }
+
+    let index = render_index(report, &by_file);
+    let path = report_dir.join("index.html");
+    fs::write(&path, index).with_context(|| format!("failed to write {:?}", path))?;
+
+    Ok(())
+}
+
+/// Page location for a source file, relative to the report directory.
+fn page_path(file: &Path) -> PathBuf {
+    let mut p = file.as_os_str().to_owned();
+    p.push(".html");
+    PathBuf::from(p)
+}
+
+/// Relative link from the report directory to a file page (always `/`-separated).
+fn page_href(file: &Path) -> String {
+    page_path(file).to_string_lossy().replace('\\', "/")
+}
+
+fn render_index(report: &MutationRunReport, by_file: &BTreeMap<PathBuf, Vec<&Mutant>>) -> String {
+    let s = &report.summary;
+    let mut body = String::new();
+
+    body.push_str("<h1>zk-mutant report</h1>\n");
+    body.push_str(&format!(
+        "<p>project: <code>{}</code><br>version: {} {}</p>\n",
+        escape(&report.project_root.display().to_string()),
+        escape(&report.tool),
+        escape(&report.version)
+    ));
+    body.push_str(&format!(
+        "<p><strong>mutation score: {}</strong> &mdash; {} executed of {} discovered; \
+         killed {}, survived {}, invalid {}, timeout {}</p>\n",
+        score_label(s.score),
+        report.executed,
+        report.discovered,
+        s.killed,
+        s.survived,
+        s.invalid,
+        s.timeout
+    ));
+    if let Some(err) = &report.error {
+        body.push_str(&format!(
+            "<p class=\"outcome survived\">error: {}</p>\n",
+            escape(err)
+        ));
+    }
+
+    body.push_str("<h2>Files (worst first)</h2>\n<table class=\"summary\">\n");
+    body.push_str(
+        "<tr><th>file</th><th>score</th><th>killed</th><th>survived</th><th>invalid</th><th>timeout</th></tr>\n",
+    );
+    for b in &report.breakdown.by_file {
+        let file = Path::new(&b.key);
+        let name = if by_file.contains_key(file) {
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape(&page_href(file)),
+                escape(&b.key)
+            )
+        } else {
+            escape(&b.key)
+        };
+        body.push_str(&format!(
+            "<tr><td>{name}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            score_label(b.score),
+            b.killed,
+            b.survived,
+            b.invalid,
+            b.timeout
+        ));
+    }
+    body.push_str("</table>\n");
+
+    page("zk-mutant report", &body)
+}
+
+fn render_file_page(
+    file: &Path,
+    text: &SourceText,
+    mutants: &[&Mutant],
+    bucket: Option<&ScoreBucket>,
+) -> String {
+    let display = file.to_string_lossy().replace('\\', "/");
+    let depth = display.matches('/').count();
+    let index_href = format!("{}index.html", "../".repeat(depth));
+
+    let mut body = String::new();
+    body.push_str(&format!(
+        "<p><a href=\"{}\">&larr; all files</a></p>\n<h1>{}</h1>\n",
+        escape(&index_href),
+        escape(&display)
+    ));
+    if let Some(b) = bucket {
+        body.push_str(&format!(
+            "<p><strong>mutation score: {}</strong> &mdash; killed {}, survived {}, invalid {}, timeout {}</p>\n",
+            score_label(b.score),
+            b.killed,
+            b.survived,
+            b.invalid,
+            b.timeout
+        ));
+    }
+
+    body.push_str(&render_source(text, mutants));
+
+    body.push_str("<h2>Mutants</h2>\n<table class=\"summary\">\n");
+    body.push_str(
+        "<tr><th>id</th><th>location</th><th>function</th><th>operator</th><th>mutation</th>\
+         <th>outcome</th><th>duration</th><th>killed by</th></tr>\n",
+    );
+    for m in mutants {
+        let location = text
+            .line_col(m.span.start as usize)
+            .map(|(l, c)| format!("<a href=\"#L{l}\">{l}:{c}</a>"))
+            .unwrap_or_else(|| format!("[{}..{}]", m.span.start, m.span.end));
+        let outcome = outcome_label(&m.outcome);
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><code>{}</code> &rarr; <code>{}</code></td>\
+             <td class=\"outcome {outcome}\">{outcome}</td><td>{}</td><td>{}</td></tr>\n",
+            m.id,
+            location,
+            escape(m.function_label().unwrap_or("-")),
+            escape(&m.operator.name),
+            escape(&m.original_snippet),
+            escape(&m.mutated_snippet),
+            duration_label(m.duration_ms),
+            escape(&m.killed_by.join(", "))
+        ));
+    }
+    body.push_str("</table>\n");
+
+    page(&display, &body)
+}
+
+/// Render highlighted source lines with a marker per mutation site.
+///
+/// Mutants sharing a span form one site; its color is the worst outcome among them and
+/// its tooltip lists every mutant.
+fn render_source(text: &SourceText, mutants: &[&Mutant]) -> String {
+    let code = text.code();
+    let classes = classify(code);
+
+    let mut sites: BTreeMap<(usize, usize), Vec<&Mutant>> = BTreeMap::new();
+    for m in mutants {
+        // Stale spans (source edited since the run) would mis-mark text or split a char.
+        let (start, end) = (m.span.start as usize, m.span.end as usize);
+        if start < end && text.matches(start, end, &m.original_snippet) {
+            sites.entry((start, end)).or_default().push(m);
+        }
+    }
+    let sites: Vec<((usize, usize), Vec<&Mutant>)> = sites.into_iter().collect();
+
+    let mut site_at: Vec<Option<usize>> = vec![None; code.len()];
+    for (idx, ((start, end), _)) in sites.iter().enumerate() {
+        for slot in &mut site_at[*start..*end] {
+            *slot = Some(idx);
+        }
+    }
+
+    let mut out = String::from("<table class=\"source\">\n");
+    let mut line_start = 0usize;
+    for (n, line) in code.split('\n').enumerate() {
+        let line_end = line_start + line.len();
+        out.push_str(&format!(
+            "<tr id=\"L{ln}\"><td class=\"ln\">{ln}</td><td>",
+            ln = n + 1
+        ));
+
+        let mut pos = line_start;
+        while pos < line_end {
+            let class = classes[pos];
+            let site = site_at[pos];
+            let mut run_end = pos + 1;
+            while run_end < line_end && classes[run_end] == class && site_at[run_end] == site {
+                run_end += 1;
+            }
+
+            let mut text = escape(&code[pos..run_end]);
+            if let Some(css) = class.css() {
+                text = format!("<span class=\"{css}\">{text}</span>");
+            }
+            if let Some(idx) = site {
+                let site_mutants = &sites[idx].1;
+                text = format!(
+                    "<span class=\"mut {}\" title=\"{}\">{text}</span>",
+                    outcome_label(worst_outcome(site_mutants)),
+                    escape(&site_tooltip(site_mutants))
+                );
+            }
+            out.push_str(&text);
+            pos = run_end;
+        }
+
+        out.push_str("</td></tr>\n");
+        line_start = line_end + 1;
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+/// Per-byte syntax classes (tokens from `syntax::tokenize`; gaps holding text are comments).
+fn classify(code: &str) -> Vec<Class> {
+    let mut classes = vec![Class::Plain; code.len()];
+
+    let mut prev_end = 0usize;
+    for tok in tokenize(code) {
+        mark_comments(code, prev_end, tok.start, &mut classes);
+
+        let text = tok.text(code);
+        let class = match tok.kind {
+            TokenKind::Ident if KEYWORDS.contains(&text) => Class::Keyword,
+            TokenKind::Ident if TYPES.contains(&text) => Class::Type,
+            TokenKind::Number => Class::Number,
+            TokenKind::Str => Class::Str,
+            _ => Class::Plain,
+        };
+        for slot in &mut classes[tok.start..tok.end] {
+            *slot = class;
+        }
+        prev_end = tok.end;
+    }
+    mark_comments(code, prev_end, code.len(), &mut classes);
+
+    classes
+}
+
+/// The tokenizer only skips whitespace and comments, so non-blank gap text is a comment.
+fn mark_comments(code: &str, start: usize, end: usize, classes: &mut [Class]) {
+    let gap = &code[start..end];
+    let trimmed = gap.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    let from = start + (gap.len() - gap.trim_start().len());
+    for slot in &mut classes[from..from + trimmed.len()] {
+        *slot = Class::Comment;
+    }
+}
+
+fn worst_outcome<'a>(mutants: &[&'a Mutant]) -> &'a MutantOutcome {
+    let rank = |o: &MutantOutcome| match o {
+        MutantOutcome::Survived => 4,
+        MutantOutcome::Timeout => 3,
+        MutantOutcome::Invalid => 2,
+        MutantOutcome::Killed => 1,
+        MutantOutcome::NotRun => 0,
+    };
+    mutants
+        .iter()
+        .map(|m| &m.outcome)
+        .max_by_key(|o| rank(o))
+        .unwrap_or(&MutantOutcome::NotRun)
+}
+
+fn site_tooltip(mutants: &[&Mutant]) -> String {
+    mutants
+        .iter()
+        .map(|m| {
+            let mut line = format!(
+                "#{} {}: {} -> {} ({}, {})",
+                m.id,
+                m.operator.name,
+                m.original_snippet,
+                m.mutated_snippet,
+                outcome_label(&m.outcome),
+                duration_label(m.duration_ms)
+            );
+            if !m.killed_by.is_empty() {
+                line.push_str(&format!("; killed by: {}", m.killed_by.join(", ")));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+/// Escape text for HTML element content and double-quoted attributes.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '\n' => out.push_str("&#10;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn mutant(id: u64, start: u32, end: u32, outcome: MutantOutcome) -> Mutant {
+        Mutant {
+            span: test_support::span("src/main.nr", start, end),
+            function: Some("main".to_string()),
+            outcome,
+            duration_ms: Some(12),
+            killed_by: vec!["test_main".to_string()],
+            ..test_support::mutant(id, "src/main.nr", "lt_to_ge", "<", ">=")
+        }
+    }
+
+    #[test]
+    fn render_source_highlights_and_marks_sites() {
+        let code = "fn main(x: u64) {\n    // x < 1\n    assert(x < 10);\n}";
+        let at = code.rfind('<').unwrap() as u32;
+        let killed = mutant(1, at, at + 1, MutantOutcome::Killed);
+        let mut survived = mutant(2, at, at + 1, MutantOutcome::Survived);
+        survived.mutated_snippet = "<=".to_string();
+        survived.killed_by.clear();
+
+        insta::assert_snapshot!(
+            "render_source",
+            render_source(&SourceText::new(code.to_string()), &[&killed, &survived])
+        );
+    }
+
+    #[test]
+    fn render_source_skips_stale_spans() {
+        let code = "fn main() {\n    let s = \"é\";\n    assert(1 < 2);\n}";
+        let accent = code.find('é').unwrap() as u32;
+        // Points into the middle of the two-byte `é`, as after an edit since the run.
+        let inside_char = mutant(1, accent + 1, accent + 2, MutantOutcome::Survived);
+        let wrong_text = mutant(2, accent, accent + 2, MutantOutcome::Survived);
+
+        let html = render_source(
+            &SourceText::new(code.to_string()),
+            &[&inside_char, &wrong_text],
+        );
+        assert!(!html.contains("class=\"mut"));
+        assert!(html.contains("é"));
+    }
+
+    #[test]
+    fn escape_handles_markup_and_newlines() {
+        assert_eq!(
+            escape("a<b && \"c\"\n'd'"),
+            "a&lt;b &amp;&amp; &quot;c&quot;&#10;&#39;d&#39;"
+        );
+    }
+
+    #[test]
+    fn page_paths_mirror_source_layout() {
+        assert_eq!(
+            page_path(Path::new("src/merkle/tree.nr")),
+            PathBuf::from("src/merkle/tree.nr.html")
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::diff::mutant_diff;
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::run_report::MutationRunReport;
+
+/// Write `junit.xml`: one `<testsuite>` per file, one `<testcase>` per mutant.
+pub fn write_junit(
+    out_dir: &Path,
+    project: &Project,
+    report: &MutationRunReport,
+    not_run: &[Mutant],
+    diff_context: usize,
+) -> Result<()> {
+    let path = out_dir.join("junit.xml");
+    let xml = render_junit(project, report, not_run, diff_context);
+    fs::write(&path, xml).with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// Render mutants as JUnit XML.
+///
+/// Outcomes map to JUnit results as follows:
+/// - killed: passed
+/// - survived: `<failure>` with the mutant's unified diff as its text
+/// - invalid / timeout: `<error>`
+/// - not run: `<skipped>`
+///
+/// `not_run` holds discovered mutants missing from the report (cut by `--limit`); they
+/// are rendered as skipped next to the report's own mutants.
+pub fn render_junit(
+    project: &Project,
+    report: &MutationRunReport,
+    not_run: &[Mutant],
+    diff_context: usize,
+) -> String {
+    let mut by_file: BTreeMap<String, Vec<&Mutant>> = BTreeMap::new();
+    for m in report.mutants.iter().chain(not_run) {
+        let file = m.span.file.to_string_lossy().replace('\\', "/");
+        by_file.entry(file).or_default().push(m);
+    }
+
+    let mut suites = String::new();
+    let mut totals = Counts::default();
+
+    for (file, mut mutants) in by_file {
+        mutants.sort_by_key(|m| m.id);
+
+        let mut counts = Counts::default();
+        let mut cases = String::new();
+        for m in mutants {
+            counts.add(m);
+            cases.push_str(&render_testcase(project, &file, m, diff_context));
+        }
+
+        suites.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n{}  </testsuite>\n",
+            escape(&file),
+            counts.tests,
+            counts.failures,
+            counts.errors,
+            counts.skipped,
+            seconds(counts.time_ms),
+            cases
+        ));
+        totals.merge(&counts);
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"{} mutation testing\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n{}</testsuites>\n",
+        escape(&report.tool),
+        totals.tests,
+        totals.failures,
+        totals.errors,
+        totals.skipped,
+        seconds(totals.time_ms),
+        suites
+    )
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+    time_ms: u64,
+}
+
+impl Counts {
+    fn add(&mut self, m: &Mutant) {
+        self.tests += 1;
+        self.time_ms += m.duration_ms.unwrap_or(0);
+        match m.outcome {
+            MutantOutcome::Survived => self.failures += 1,
+            MutantOutcome::Invalid | MutantOutcome::Timeout => self.errors += 1,
+            MutantOutcome::NotRun => self.skipped += 1,
+            MutantOutcome::Killed => {}
+        }
+    }
+
+    fn merge(&mut self, other: &Counts) {
+        self.tests += other.tests;
+        self.failures += other.failures;
+        self.errors += other.errors;
+        self.skipped += other.skipped;
+        self.time_ms += other.time_ms;
+    }
+}
+
+fn render_testcase(project: &Project, file: &str, m: &Mutant, diff_context: usize) -> String {
+    let name = format!(
+        "{}::{} #{}",
+        m.function_label().unwrap_or("<top-level>"),
+        m.operator.name,
+        m.id
+    );
+    let open = format!(
+        "    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+        escape(file),
+        escape(&name),
+        seconds(m.duration_ms.unwrap_or(0))
+    );
+    let change = format!("{} -> {}", m.original_snippet, m.mutated_snippet);
+
+    let body = match m.outcome {
+        MutantOutcome::Killed => return format!("{open}/>\n"),
+        MutantOutcome::Survived => {
+            let diff = mutant_diff(project, m, diff_context).unwrap_or_default();
+            format!(
+                "      <failure type=\"survived\" message=\"mutant survived: {}\">{}</failure>\n",
+                escape(&change),
+                escape(&diff)
+            )
+        }
+        MutantOutcome::Invalid => format!(
+            "      <error type=\"invalid\" message=\"mutant could not be built or tested: {}\"/>\n",
+            escape(&change)
+        ),
+        MutantOutcome::Timeout => format!(
+            "      <error type=\"timeout\" message=\"mutant timed out: {}\"/>\n",
+            escape(&change)
+        ),
+        MutantOutcome::NotRun => "      <skipped message=\"mutant not run\"/>\n".to_string(),
+    };
+
+    format!("{open}>\n{body}    </testcase>\n")
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+/// Escape text for XML content and attributes; drops characters XML 1.0 cannot represent.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_report;
+
+    #[test]
+    fn render_junit_snapshot_fixture() {
+        let (project, mut report) = fixture_report(&[
+            MutantOutcome::Killed,
+            MutantOutcome::Survived,
+            MutantOutcome::Invalid,
+        ]);
+        for (m, ms) in report.mutants.iter_mut().zip([12, 1500, 3]) {
+            m.duration_ms = Some(ms);
+        }
+
+        // The last mutant was cut by `--limit`, so it is not part of the report.
+        let not_run = report.mutants.split_off(3);
+
+        insta::assert_snapshot!("render_junit", render_junit(&project, &report, &not_run, 1));
+    }
+
+    #[test]
+    fn escape_drops_invalid_control_characters() {
+        assert_eq!(escape("a<b>&\"'\u{1}\n"), "a&lt;b&gt;&amp;&quot;&apos;\n");
+    }
+}
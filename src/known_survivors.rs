@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::mutant::{Mutant, MutantOutcome};
+
+const KNOWN_SURVIVORS_FORMAT_VERSION: u32 = 1;
+
+/// One accepted survivor in a checked-in baseline file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KnownSurvivor {
+    /// Mutant fingerprint (see `Mutant::fingerprint`).
+    pub fingerprint: String,
+
+    /// Why this survivor is accepted (free text, for reviewers).
+    #[serde(default)]
+    pub reason: String,
+
+    /// Informational: file of the mutant when the entry was written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+
+    /// Informational: operator name when the entry was written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KnownSurvivorsFile {
+    version: u32,
+    survivors: Vec<KnownSurvivor>,
+}
+
+/// Survivors accepted by a baseline file, keyed by fingerprint.
+#[derive(Debug, Clone, Default)]
+pub struct KnownSurvivors {
+    entries: BTreeMap<String, KnownSurvivor>,
+}
+
+/// How a run's survivors compare to a baseline file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownSurvivorsSummary {
+    /// Path of the baseline file.
+    pub path: PathBuf,
+
+    /// Survivors listed in the baseline.
+    pub accepted: usize,
+
+    /// Survivors not listed in the baseline (these fail `--fail-on-survivors`).
+    pub new: usize,
+
+    /// Baseline entries whose mutant was killed or no longer exists.
+    pub stale: usize,
+}
+
+impl KnownSurvivors {
+    /// Load a baseline file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+        let file: KnownSurvivorsFile = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse survivors baseline {:?}", path))?;
+
+        if file.version != KNOWN_SURVIVORS_FORMAT_VERSION {
+            anyhow::bail!(
+                "unsupported survivors baseline version {} in {:?} (expected {})",
+                file.version,
+                path,
+                KNOWN_SURVIVORS_FORMAT_VERSION
+            );
+        }
+
+        let entries = file
+            .survivors
+            .into_iter()
+            .map(|e| (e.fingerprint.clone(), e))
+            .collect();
+
+        Ok(Self { entries })
+    }
+
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.entries.contains_key(fingerprint)
+    }
+
+    /// Compare executed mutants against the baseline.
+    ///
+    /// `discovered` holds the fingerprints of all discovered mutants (pre-limit), so
+    /// entries for mutants that simply were not executed are not reported as stale.
+    pub fn summarize(
+        &self,
+        path: &Path,
+        executed: &[Mutant],
+        discovered: &BTreeSet<String>,
+    ) -> KnownSurvivorsSummary {
+        let mut accepted = 0;
+        let mut new = 0;
+
+        for m in executed
+            .iter()
+            .filter(|m| m.outcome == MutantOutcome::Survived)
+        {
+            if self.contains(&m.fingerprint) {
+                accepted += 1;
+            } else {
+                new += 1;
+            }
+        }
+
+        let executed_non_survivors: BTreeSet<&str> = executed
+            .iter()
+            .filter(|m| m.outcome != MutantOutcome::Survived)
+            .map(|m| m.fingerprint.as_str())
+            .collect();
+
+        let stale = self
+            .entries
+            .keys()
+            .filter(|fp| {
+                executed_non_survivors.contains(fp.as_str()) || !discovered.contains(fp.as_str())
+            })
+            .count();
+
+        KnownSurvivorsSummary {
+            path: path.to_path_buf(),
+            accepted,
+            new,
+            stale,
+        }
+    }
+
+    /// Build the baseline that accepts exactly the survivors of this run.
+    ///
+    /// Reasons of existing entries are preserved. Entries for discovered mutants that
+    /// were not executed (for example because of `--limit`) are kept unchanged.
+    pub fn updated(&self, executed: &[Mutant], discovered: &BTreeSet<String>) -> Self {
+        let executed_fps: BTreeSet<&str> =
+            executed.iter().map(|m| m.fingerprint.as_str()).collect();
+
+        let mut entries: BTreeMap<String, KnownSurvivor> = self
+            .entries
+            .iter()
+            .filter(|(fp, _)| {
+                discovered.contains(fp.as_str()) && !executed_fps.contains(fp.as_str())
+            })
+            .map(|(fp, e)| (fp.clone(), e.clone()))
+            .collect();
+
+        for m in executed
+            .iter()
+            .filter(|m| m.outcome == MutantOutcome::Survived)
+        {
+            let reason = self
+                .entries
+                .get(&m.fingerprint)
+                .map(|e| e.reason.clone())
+                .unwrap_or_default();
+
+            entries.insert(
+                m.fingerprint.clone(),
+                KnownSurvivor {
+                    fingerprint: m.fingerprint.clone(),
+                    reason,
+                    file: Some(m.span.file.clone()),
+                    operator: Some(m.operator.name.clone()),
+                },
+            );
+        }
+
+        Self { entries }
+    }
+
+    /// Write the baseline file (entries ordered by file, then fingerprint).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut survivors: Vec<KnownSurvivor> = self.entries.values().cloned().collect();
+        survivors.sort_by(|a, b| (&a.file, &a.fingerprint).cmp(&(&b.file, &b.fingerprint)));
+
+        let file = KnownSurvivorsFile {
+            version: KNOWN_SURVIVORS_FORMAT_VERSION,
+            survivors,
+        };
+
+        let json = serde_json::to_string_pretty(&file).context("serialize survivors baseline")?;
+        fs::write(path, json + "\n").with_context(|| format!("failed to write {:?}", path))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+    use tempfile::TempDir;
+
+    fn mutant(fp: &str, outcome: MutantOutcome) -> Mutant {
+        Mutant {
+            fingerprint: fp.to_string(),
+            outcome,
+            duration_ms: Some(1),
+            ..test_support::mutant(1, "src/main.nr", "eq_to_neq", "==", "!=")
+        }
+    }
+
+    fn baseline(fps: &[(&str, &str)]) -> KnownSurvivors {
+        KnownSurvivors {
+            entries: fps
+                .iter()
+                .map(|(fp, reason)| {
+                    (
+                        fp.to_string(),
+                        KnownSurvivor {
+                            fingerprint: fp.to_string(),
+                            reason: reason.to_string(),
+                            file: None,
+                            operator: None,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn summarize_splits_accepted_new_and_stale() {
+        let known = baseline(&[("a", "ok"), ("k", "was a survivor"), ("gone", "")]);
+        let executed = vec![
+            mutant("a", MutantOutcome::Survived),
+            mutant("b", MutantOutcome::Survived),
+            mutant("k", MutantOutcome::Killed),
+        ];
+        let discovered: BTreeSet<String> = ["a", "b", "k", "unrun"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let s = known.summarize(Path::new("b.json"), &executed, &discovered);
+        assert_eq!((s.accepted, s.new, s.stale), (1, 1, 2));
+    }
+
+    #[test]
+    fn updated_keeps_reasons_and_unexecuted_entries() {
+        let known = baseline(&[("a", "accepted: dead code"), ("k", ""), ("unrun", "later")]);
+        let executed = vec![
+            mutant("a", MutantOutcome::Survived),
+            mutant("b", MutantOutcome::Survived),
+            mutant("k", MutantOutcome::Killed),
+        ];
+        let discovered: BTreeSet<String> = ["a", "b", "k", "unrun"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let updated = known.updated(&executed, &discovered);
+        let fps: Vec<&str> = updated.entries.keys().map(|s| s.as_str()).collect();
+        assert_eq!(fps, vec!["a", "b", "unrun"]);
+        assert_eq!(updated.entries["a"].reason, "accepted: dead code");
+        assert_eq!(updated.entries["b"].reason, "");
+        assert_eq!(updated.entries["unrun"].reason, "later");
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().join("survivors.json");
+
+        let known = baseline(&[("a", "reason a")]);
+        known.save(&path).unwrap();
+
+        let loaded = KnownSurvivors::load(&path).unwrap();
+        assert!(loaded.contains("a"));
+        assert_eq!(loaded.entries["a"].reason, "reason a");
+    }
+}
@@ -0,0 +1,156 @@
+use std::ops::Range;
+
+use crate::span::{LineColumn, SpanLocation};
+
+/// Line start offsets of a source file, for turning byte offsets into 1-based
+/// line/column positions and for slicing whole lines.
+///
+/// Columns count characters (not bytes), matching what editors and SARIF expect.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    code: &'a str,
+
+    /// Byte offset where each line starts; always begins with `0`.
+    line_starts: &'a [usize],
+}
+
+impl<'a> LineIndex<'a> {
+    /// Index over `code` using a table built by [`line_starts`] for the same text.
+    pub fn new(code: &'a str, line_starts: &'a [usize]) -> Self {
+        Self { code, line_starts }
+    }
+
+    /// The indexed text.
+    pub fn code(&self) -> &'a str {
+        self.code
+    }
+
+    /// 1-based `(line, column)` of a byte offset; `None` past the end of the file or
+    /// inside a multi-byte character.
+    pub fn line_col(&self, offset: usize) -> Option<(usize, usize)> {
+        if !self.code.is_char_boundary(offset) {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|&s| s <= offset);
+        let start = self.line_starts[line - 1];
+        let col = self.code[start..offset].chars().count() + 1;
+        Some((line, col))
+    }
+
+    /// Byte range of a 1-based line, without its line terminator.
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map(|next| next - 1)
+            .unwrap_or(self.code.len());
+        let end = if self.code[start..end].ends_with('\r') {
+            end - 1
+        } else {
+            end
+        };
+        Some(start..end)
+    }
+
+    /// Text of a 1-based line, without its line terminator.
+    pub fn line_text(&self, line: usize) -> Option<&'a str> {
+        self.line_range(line).map(|r| &self.code[r])
+    }
+
+    /// Number of lines (a trailing newline does not start a new line).
+    pub fn line_count(&self) -> usize {
+        if self.code.ends_with('\n') {
+            self.line_starts.len() - 1
+        } else {
+            self.line_starts.len()
+        }
+    }
+
+    /// Line/column range of `start..end` together with the source line and the same
+    /// line after replacing the span with `replacement`.
+    ///
+    /// For spans over several lines, `source_line` and `mutated_line` run from the start
+    /// of the first line to the end of the last.
+    pub fn locate(&self, start: usize, end: usize, replacement: &str) -> Option<SpanLocation> {
+        if start > end {
+            return None;
+        }
+        let (start_line, start_col) = self.line_col(start)?;
+        let (end_line, end_col) = self.line_col(end)?;
+        let first = self.line_range(start_line)?;
+        let last = self.line_range(end_line)?;
+        let line_end = last.end.max(end);
+
+        Some(SpanLocation {
+            start: LineColumn {
+                line: start_line,
+                column: start_col,
+            },
+            end: LineColumn {
+                line: end_line,
+                column: end_col,
+            },
+            source_line: self.code[first.start..line_end].to_string(),
+            mutated_line: format!(
+                "{}{replacement}{}",
+                &self.code[first.start..start],
+                &self.code[end..line_end]
+            ),
+        })
+    }
+}
+
+/// Byte offset where each line of `code` starts; always begins with `0`.
+pub fn line_starts(code: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        code.bytes()
+            .enumerate()
+            .filter(|(_, b)| *b == b'\n')
+            .map(|(i, _)| i + 1),
+    );
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_col_and_line_text() {
+        let code = "a\r\nbéd\nef";
+        let starts = line_starts(code);
+        let index = LineIndex::new(code, &starts);
+
+        assert_eq!(index.line_col(0), Some((1, 1)));
+        assert_eq!(index.line_col(3), Some((2, 1)));
+        assert_eq!(index.line_col(6), Some((2, 3)));
+        assert_eq!(index.line_col(5), None, "inside `é`");
+        assert_eq!(index.line_col(code.len()), Some((3, 3)));
+        assert_eq!(index.line_col(code.len() + 1), None);
+
+        assert_eq!(index.line_text(1), Some("a"));
+        assert_eq!(index.line_text(2), Some("béd"));
+        assert_eq!(index.line_text(3), Some("ef"));
+        assert_eq!(index.line_text(4), None);
+        assert_eq!(index.line_text(0), None);
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(LineIndex::new("x\n", &line_starts("x\n")).line_count(), 1);
+    }
+
+    #[test]
+    fn locate_reports_lines_before_and_after() {
+        let code = "fn f() {\n    assert(x == y);\n}\n";
+        let start = code.find("==").unwrap();
+        let starts = line_starts(code);
+        let loc = LineIndex::new(code, &starts)
+            .locate(start, start + 2, "!=")
+            .unwrap();
+
+        assert_eq!((loc.start.line, loc.start.column), (2, 14));
+        assert_eq!((loc.end.line, loc.end.column), (2, 16));
+        assert_eq!(loc.source_line, "    assert(x == y);");
+        assert_eq!(loc.mutated_line, "    assert(x != y);");
+    }
+}
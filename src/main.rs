@@ -1,17 +1,41 @@
+mod annotations;
+mod apply;
+mod cache;
+mod cargo_mutants;
 mod cli;
+mod compare;
+mod config;
+mod diff;
 mod discover;
+mod export;
+mod hash;
+mod html;
+mod junit;
+mod known_survivors;
+mod line_index;
+mod markdown;
 mod mutant;
+mod mutant_dir;
 mod nargo;
 mod options;
 mod out;
 mod patch;
+mod policy;
 mod project;
 mod report;
 mod run_report;
 mod runner;
+mod sarif;
 mod scan;
+mod show;
 mod source;
+mod source_map;
 mod span;
+mod stryker;
+mod suggest;
+mod syntax;
+#[cfg(test)]
+mod test_support;
 mod ui;
 
 /// Entry point for the `zk-mutant` binary.
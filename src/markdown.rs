@@ -0,0 +1,233 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::report::score_label;
+use crate::run_report::MutationRunReport;
+use crate::source_map::SourceText;
+
+/// Environment variable GitHub Actions sets to the job's step summary file.
+pub const STEP_SUMMARY_ENV: &str = "GITHUB_STEP_SUMMARY";
+
+/// Render a compact Markdown report for pull-request comments and step summaries.
+///
+/// The report has a score badge line, a per-file table and a collapsible list of
+/// survivors with a small before/after excerpt each. When `link_base` is set (see
+/// [`github_blob_base`]), survivor locations link to `<link_base><file>#L<line>`.
+pub fn render_markdown(
+    project: &Project,
+    report: &MutationRunReport,
+    link_base: Option<&str>,
+) -> String {
+    let summary = &report.summary;
+    let score = score_label(summary.score);
+
+    let mut out = String::new();
+    out.push_str(&format!("## {} mutation testing\n\n", report.tool));
+    out.push_str(&format!(
+        "![mutation score](https://img.shields.io/badge/mutation%20score-{}-{}) \
+         **{}** — {} killed, {} survived, {} invalid, {} timeout ({} of {} mutants run)\n",
+        badge_escape(&score),
+        badge_color(summary.score),
+        score,
+        summary.killed,
+        summary.survived,
+        summary.invalid,
+        summary.timeout,
+        report.executed,
+        report.discovered
+    ));
+
+    if let Some(err) = &report.error {
+        out.push_str(&format!("\n**Error:** {}\n", err));
+    }
+    for v in &report.policy_violations {
+        out.push_str(&format!("\n**Policy failed:** {}\n", v.message));
+    }
+
+    if !report.breakdown.by_file.is_empty() {
+        out.push_str("\n| File | Score | Killed | Survived | Invalid | Timeout |\n");
+        out.push_str("| --- | ---: | ---: | ---: | ---: | ---: |\n");
+        for b in &report.breakdown.by_file {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} | {} |\n",
+                b.key.replace('\\', "/").replace('|', "\\|"),
+                score_label(b.score),
+                b.killed,
+                b.survived,
+                b.invalid,
+                b.timeout
+            ));
+        }
+    }
+
+    let mut survivors: Vec<&Mutant> = report
+        .mutants
+        .iter()
+        .filter(|m| m.outcome == MutantOutcome::Survived)
+        .collect();
+    survivors.sort_by_key(|m| m.id);
+
+    if survivors.is_empty() {
+        if summary.killed > 0 {
+            out.push_str("\nNo surviving mutants.\n");
+        }
+        return out;
+    }
+
+    out.push_str(&format!(
+        "\n<details>\n<summary>{} surviving mutant{}</summary>\n\n",
+        survivors.len(),
+        if survivors.len() == 1 { "" } else { "s" }
+    ));
+    for m in survivors {
+        render_survivor(&mut out, project, m, link_base);
+    }
+    out.push_str("</details>\n");
+
+    out
+}
+
+fn render_survivor(out: &mut String, project: &Project, m: &Mutant, link_base: Option<&str>) {
+    let file = m.span.file.to_string_lossy().replace('\\', "/");
+    let text = project.sources().get(&m.span.file);
+    let line_col = text.and_then(|t| t.line_col(m.span.start as usize));
+
+    let location = match line_col {
+        Some((line, col)) => {
+            let label = format!("`{file}:{line}:{col}`");
+            match link_base {
+                Some(base) => format!("[{label}]({base}{file}#L{line})"),
+                None => label,
+            }
+        }
+        None => format!("`{file}`"),
+    };
+    let place = match m.function_label() {
+        Some(f) => format!(" in `{f}`"),
+        None => String::new(),
+    };
+
+    out.push_str(&format!(
+        "- {location}{place}: `{}` → `{}` (#{})\n",
+        m.original_snippet, m.mutated_snippet, m.id
+    ));
+
+    if let Some((before, after)) = text.and_then(|t| excerpt(t, m)) {
+        out.push_str(&format!("\n  ```diff\n  -{before}\n  +{after}\n  ```\n\n"));
+    }
+}
+
+/// The mutated line before and after the change, with its indentation removed.
+fn excerpt(text: &SourceText, m: &Mutant) -> Option<(String, String)> {
+    let start = m.span.start as usize;
+    let end = m.span.end as usize;
+    if !text.matches(start, end, &m.original_snippet) {
+        return None;
+    }
+
+    let loc = text.index().locate(start, end, &m.mutated_snippet)?;
+    Some((
+        loc.source_line.trim().to_string(),
+        loc.mutated_line.trim().to_string(),
+    ))
+}
+
+fn badge_color(score: Option<f64>) -> &'static str {
+    match score {
+        Some(s) if s >= 80.0 => "brightgreen",
+        Some(s) if s >= 60.0 => "yellow",
+        Some(_) => "red",
+        None => "lightgrey",
+    }
+}
+
+/// Escape a shields.io static badge segment (`-` and `_` are separators there).
+fn badge_escape(text: &str) -> String {
+    text.replace('-', "--")
+        .replace('_', "__")
+        .replace('%', "%25")
+        .replace('/', "%2F")
+        .replace(' ', "%20")
+}
+
+/// Blob URL prefix for the checked-out commit when running in GitHub Actions.
+///
+/// Built from `GITHUB_SERVER_URL`, `GITHUB_REPOSITORY` and `GITHUB_SHA`, plus the project
+/// root's path inside `GITHUB_WORKSPACE`; `None` outside Actions.
+pub fn github_blob_base(project_root: &Path) -> Option<String> {
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let server = var("GITHUB_SERVER_URL")?;
+    let repo = var("GITHUB_REPOSITORY")?;
+    let sha = var("GITHUB_SHA")?;
+    let workspace = var("GITHUB_WORKSPACE")?;
+
+    let root = fs::canonicalize(project_root).ok()?;
+    let workspace = fs::canonicalize(workspace).ok()?;
+    let rel = root.strip_prefix(&workspace).ok()?;
+
+    let mut base = format!("{}/{repo}/blob/{sha}/", server.trim_end_matches('/'));
+    for part in rel.components() {
+        base.push_str(&part.as_os_str().to_string_lossy());
+        base.push('/');
+    }
+    Some(base)
+}
+
+/// Write the Markdown report to `path`, creating parent directories.
+pub fn write_markdown(path: &Path, markdown: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).with_context(|| format!("failed to create {:?}", parent))?;
+        }
+    }
+    fs::write(path, markdown).with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// Append the Markdown report to a step summary file (other steps may have written to it).
+pub fn append_step_summary(path: &Path, markdown: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {:?}", path))?;
+    writeln!(file, "{markdown}").with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_report;
+
+    #[test]
+    fn render_markdown_snapshot_fixture() {
+        let (project, report) = fixture_report(&[
+            MutantOutcome::Killed,
+            MutantOutcome::Survived,
+            MutantOutcome::Killed,
+            MutantOutcome::Survived,
+        ]);
+
+        insta::assert_snapshot!(
+            "render_markdown",
+            render_markdown(
+                &project,
+                &report,
+                Some("https://github.com/o/r/blob/abc123/")
+            )
+        );
+    }
+
+    #[test]
+    fn badge_escape_handles_shields_separators() {
+        assert_eq!(badge_escape("50.00%"), "50.00%25");
+        assert_eq!(badge_escape("n/a"), "n%2Fa");
+        assert_eq!(badge_escape("a-b_c d"), "a--b__c%20d");
+    }
+}
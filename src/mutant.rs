@@ -1,4 +1,4 @@
-use crate::span::SourceSpan;
+use crate::span::{SourceSpan, SpanLocation};
 use serde::{Deserialize, Serialize};
 
 /// Category of a mutation operator.
@@ -41,6 +41,9 @@ pub enum MutantOutcome {
 
     /// Mutant could not be built or executed.
     Invalid,
+
+    /// Tests did not finish within the configured timeout.
+    Timeout,
 }
 
 /// Representation of a single first-order mutant at the Noir source level.
@@ -49,12 +52,42 @@ pub struct Mutant {
     /// Monotonically increasing identifier.
     pub id: u64,
 
+    /// Content-based fingerprint that stays stable across unrelated edits.
+    ///
+    /// Derived from the file, enclosing function, operator, the surrounding tokens and
+    /// the occurrence index among otherwise identical sites (see `discover`).
+    #[serde(default)]
+    pub fingerprint: String,
+
     /// Operator applied to create this mutant.
     pub operator: MutationOperator,
 
     /// Location of the mutated snippet in the source code.
     pub span: SourceSpan,
 
+    /// Name of the function containing the mutated snippet, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+
+    /// Module path of the mutated code relative to the crate root (for example `utils`
+    /// for `src/utils.nr`), including inline `mod` blocks; `None` at the crate root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module_path: Option<String>,
+
+    /// Header of the enclosing `impl` or `trait` block, if any (for example
+    /// `impl Default for Point<Field>`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impl_context: Option<String>,
+
+    /// Module path, `impl` self type or trait, and function joined with `::` (for example
+    /// `utils::check_addition` or `math::Point::eq`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+
+    /// Line/column range and the source line before and after mutation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SpanLocation>,
+
     /// Original source snippet (before mutation).
     pub original_snippet: String,
 
@@ -68,4 +101,18 @@ pub struct Mutant {
     ///
     /// `None` means the mutant has not been executed.
     pub duration_ms: Option<u64>,
+
+    /// Tests reported as failing by `nargo test` when this mutant was killed.
+    ///
+    /// Empty when the mutant was not killed or the output could not be parsed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub killed_by: Vec<String>,
+}
+
+impl Mutant {
+    /// Qualified name of the enclosing function (for example `utils::check_addition`),
+    /// falling back to the bare name for reports written before it was recorded.
+    pub fn function_label(&self) -> Option<&str> {
+        self.qualified_name.as_deref().or(self.function.as_deref())
+    }
 }
@@ -0,0 +1,306 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::diff::unified_diff;
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::nargo::NargoTestResult;
+use crate::patch::apply_checked_patch;
+use crate::project::Project;
+use crate::report::{format_mutant_with_location, outcome_label};
+use crate::ui::Ui;
+
+/// Default cap on captured `nargo test` output, per stream.
+pub const DEFAULT_MAX_LOG_BYTES: usize = 64 * 1024;
+
+/// Where and how much `nargo test` output to keep for each executed mutant.
+#[derive(Debug, Clone)]
+pub struct LogCapture {
+    /// Output directory; logs go to `<out_dir>/mutants/<id>/nargo.log`.
+    pub out_dir: PathBuf,
+
+    /// Keep at most this many trailing bytes of stdout and of stderr (0 keeps everything).
+    pub max_bytes: usize,
+}
+
+/// `<out_dir>/mutants/<id>/` for one mutant (ids zero-padded like `diff/`).
+pub fn mutant_dir(out_dir: &Path, id: u64) -> PathBuf {
+    out_dir.join("mutants").join(format!("{id:06}"))
+}
+
+/// Write `nargo.log` with the captured output of the mutant's `nargo test` run.
+pub fn write_nargo_log(
+    capture: &LogCapture,
+    project: &Project,
+    m: &Mutant,
+    result: &NargoTestResult,
+) -> Result<()> {
+    let mut log = log_header(project, m);
+    log.push_str(&format!(
+        "exit_code: {}\ntimed_out: {}\nduration_ms: {}\n",
+        result
+            .exit_code
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        result.timed_out,
+        result.duration.as_millis()
+    ));
+    log.push_str("--- stdout ---\n");
+    push_capped(&mut log, &result.stdout, capture.max_bytes);
+    log.push_str("--- stderr ---\n");
+    push_capped(&mut log, &result.stderr, capture.max_bytes);
+
+    write_log(capture, m, &log)
+}
+
+/// Write `nargo.log` for a mutant that could not be run at all.
+pub fn write_error_log(
+    capture: &LogCapture,
+    project: &Project,
+    m: &Mutant,
+    error: &str,
+) -> Result<()> {
+    let mut log = log_header(project, m);
+    log.push_str(&format!("error: {error}\n"));
+    write_log(capture, m, &log)
+}
+
+/// Write `nargo.log` for a mutant whose outcome came from the result cache.
+pub fn write_cached_log(capture: &LogCapture, project: &Project, m: &Mutant) -> Result<()> {
+    let mut log = log_header(project, m);
+    log.push_str("outcome reused from the result cache; run with --no-cache to capture output\n");
+    write_log(capture, m, &log)
+}
+
+fn log_header(project: &Project, m: &Mutant) -> String {
+    format!(
+        "mutant: {}\noutcome: {}\ncommand: nargo test\n",
+        format_mutant_with_location(project, m),
+        outcome_label(&m.outcome)
+    )
+}
+
+fn write_log(capture: &LogCapture, m: &Mutant, log: &str) -> Result<()> {
+    let dir = mutant_dir(&capture.out_dir, m.id);
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {:?}", dir))?;
+    let path = dir.join("nargo.log");
+    fs::write(&path, log).with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// Append `text` (newline-terminated), keeping only its last `max_bytes` bytes.
+fn push_capped(out: &mut String, text: &str, max_bytes: usize) {
+    let mut start = 0;
+    if max_bytes > 0 && text.len() > max_bytes {
+        start = text.len() - max_bytes;
+        while !text.is_char_boundary(start) {
+            start += 1;
+        }
+        out.push_str(&format!("[... {start} bytes omitted ...]\n"));
+    }
+
+    let kept = &text[start..];
+    out.push_str(kept);
+    if !kept.is_empty() && !kept.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Write the mutated file, `mutant.diff` and `repro.sh` for every executed mutant.
+///
+/// Mutants whose source no longer matches are skipped with a warning.
+pub fn write_mutant_dirs(
+    out_dir: &Path,
+    project: &Project,
+    mutants: &[Mutant],
+    diff_context: usize,
+    ui: &Ui,
+) -> Result<()> {
+    let root = fs::canonicalize(project.root())
+        .with_context(|| format!("failed to resolve {:?}", project.root()))?;
+
+    let mut ordered: Vec<&Mutant> = mutants
+        .iter()
+        .filter(|m| m.outcome != MutantOutcome::NotRun)
+        .collect();
+    ordered.sort_by_key(|m| m.id);
+
+    for m in ordered {
+        let original = match original_source(project, m) {
+            Ok(original) => original,
+            Err(e) => {
+                ui.warn(format!("skipping mutant dir for mutant {}: {e}", m.id));
+                continue;
+            }
+        };
+        let mutated =
+            apply_checked_patch(original, &m.span, &m.original_snippet, &m.mutated_snippet);
+
+        let dir = mutant_dir(out_dir, m.id);
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {:?}", dir))?;
+
+        let file_name = mutated_file_name(m);
+        let path = dir.join(&file_name);
+        fs::write(&path, &mutated).with_context(|| format!("failed to write {:?}", path))?;
+
+        let path = dir.join("mutant.diff");
+        let diff = unified_diff(&m.span.file, original, &mutated, diff_context);
+        fs::write(&path, diff).with_context(|| format!("failed to write {:?}", path))?;
+
+        let path = dir.join("repro.sh");
+        let script = render_repro(&root, project, m, &file_name);
+        fs::write(&path, script).with_context(|| format!("failed to write {:?}", path))?;
+        make_executable(&path)?;
+    }
+
+    Ok(())
+}
+
+/// The current source of the mutant's file, checked against the recorded snippet.
+fn original_source<'a>(project: &'a Project, m: &Mutant) -> Result<&'a str> {
+    let text = project
+        .sources()
+        .get(&m.span.file)
+        .ok_or_else(|| anyhow::anyhow!("source file {:?} not part of project", m.span.file))?;
+    if !text.matches(
+        m.span.start as usize,
+        m.span.end as usize,
+        &m.original_snippet,
+    ) {
+        anyhow::bail!(
+            "no longer matches {:?} (source changed since discovery?)",
+            m.span.file
+        );
+    }
+    Ok(text.code())
+}
+
+fn mutated_file_name(m: &Mutant) -> String {
+    m.span
+        .file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mutated.nr".to_string())
+}
+
+/// Shell script that copies the project to a temp dir, swaps in the mutated file and
+/// runs `nargo test` there (the project root can be overridden by the first argument).
+fn render_repro(root: &Path, project: &Project, m: &Mutant, file_name: &str) -> String {
+    let rel = m.span.file.to_string_lossy().replace('\\', "/");
+    format!(
+        "#!/usr/bin/env bash\n\
+         # Reproduce {description}\n\
+         #\n\
+         # Copies the project to a temporary directory, replaces {rel} with the mutated\n\
+         # copy next to this script and runs `nargo test` there.\n\
+         # Usage: repro.sh [PROJECT_ROOT]\n\
+         set -euo pipefail\n\
+         \n\
+         here=\"$(cd \"$(dirname \"${{BASH_SOURCE[0]}}\")\" && pwd)\"\n\
+         default_project={root}\n\
+         project=\"${{1:-$default_project}}\"\n\
+         work=\"$(mktemp -d)\"\n\
+         \n\
+         cp -R \"$project/.\" \"$work/\"\n\
+         cp \"$here/\"{file} \"$work/\"{rel_quoted}\n\
+         \n\
+         echo \"mutated tree: $work\" >&2\n\
+         cd \"$work\"\n\
+         exec nargo test\n",
+        description = format_mutant_with_location(project, m),
+        root = shell_quote(&root.to_string_lossy()),
+        file = shell_quote(file_name),
+        rel_quoted = shell_quote(&rel),
+    )
+}
+
+/// Single-quote `s` for POSIX shells.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut perms = fs::metadata(path)
+        .with_context(|| format!("failed to stat {:?}", path))?
+        .permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms).with_context(|| format!("failed to chmod {:?}", path))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+    use crate::test_support::fixture_report;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn push_capped_keeps_the_tail_on_char_boundaries() {
+        let mut out = String::new();
+        push_capped(&mut out, "hello world", 5);
+        assert_eq!(out, "[... 6 bytes omitted ...]\nworld\n");
+
+        // `é` is two bytes; the cut moves forward to the next character.
+        let mut out = String::new();
+        push_capped(&mut out, "ééé", 3);
+        assert_eq!(out, "[... 4 bytes omitted ...]\né\n");
+
+        let mut out = String::new();
+        push_capped(&mut out, "short\n", 0);
+        assert_eq!(out, "short\n");
+    }
+
+    #[test]
+    fn nargo_log_records_command_status_and_output() {
+        let project = Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load");
+        let mut mutants = discover_mutants(&project);
+        mutants[0].outcome = MutantOutcome::Killed;
+
+        let td = TempDir::new().unwrap();
+        let capture = LogCapture {
+            out_dir: td.path().to_path_buf(),
+            max_bytes: DEFAULT_MAX_LOG_BYTES,
+        };
+        let result = NargoTestResult {
+            exit_code: Some(1),
+            success: false,
+            stdout: "[simple_noir] Testing test_main... FAIL\n".to_string(),
+            stderr: "assertion failed".to_string(),
+            duration: Duration::from_millis(42),
+            timed_out: false,
+        };
+        write_nargo_log(&capture, &project, &mutants[0], &result).unwrap();
+
+        let log = fs::read_to_string(td.path().join("mutants/000001/nargo.log")).unwrap();
+        insta::assert_snapshot!("nargo_log", log);
+    }
+
+    #[test]
+    fn mutant_dirs_skip_stale_mutants() {
+        let (project, mut report) =
+            fixture_report(&[MutantOutcome::Killed, MutantOutcome::Survived]);
+        report.mutants[0].original_snippet = "<stale>".to_string();
+
+        let td = TempDir::new().unwrap();
+        write_mutant_dirs(td.path(), &project, &report.mutants, 3, &Ui::silent()).unwrap();
+
+        assert!(!mutant_dir(td.path(), 1).exists());
+        assert!(mutant_dir(td.path(), 2).join("repro.sh").exists());
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
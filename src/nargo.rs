@@ -1,7 +1,9 @@
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 use std::process::{Command, Stdio};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 
@@ -22,11 +24,141 @@ pub struct NargoTestResult {
 
     /// How long the command ran.
     pub duration: Duration,
+
+    /// Was the command killed because it exceeded its timeout?
+    pub timed_out: bool,
+}
+
+impl NargoTestResult {
+    /// Names of tests reported as failing, in output order.
+    ///
+    /// Parses nargo's per-test progress lines, for example
+    /// `[my_pkg] Testing test_transfer... FAIL` (ANSI colors are ignored).
+    pub fn failed_tests(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        for line in self.stdout.lines().chain(self.stderr.lines()) {
+            if let Some(name) = failed_test_name(&strip_ansi(line)) {
+                if !out.contains(&name) {
+                    out.push(name);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Parse `[pkg] Testing <name>... FAIL` / `Testing <name> ... FAIL`.
+fn failed_test_name(line: &str) -> Option<String> {
+    let rest = line.trim();
+    let rest = match rest.strip_prefix('[') {
+        Some(r) => r.split_once(']')?.1.trim_start(),
+        None => rest,
+    };
+    let rest = rest.strip_prefix("Testing ")?;
+    let (name, status) = rest.split_once("...")?;
+    if !status.trim().eq_ignore_ascii_case("fail") {
+        return None;
+    }
+
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
 }
 
+/// Remove ANSI escape sequences such as `\x1b[31m`.
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// How often a running `nargo test` is polled while waiting for a timeout.
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Run `nargo test` in the given project directory.
 pub fn run_nargo_test(project_root: &Path) -> Result<NargoTestResult> {
-    let start = std::time::Instant::now();
+    run_nargo_test_with_timeout(project_root, None)
+}
+
+/// Run `nargo test`, killing it if it runs longer than `timeout` (when set).
+pub fn run_nargo_test_with_timeout(
+    project_root: &Path,
+    timeout: Option<Duration>,
+) -> Result<NargoTestResult> {
+    let Some(timeout) = timeout else {
+        return run_nargo_test_to_completion(project_root);
+    };
+
+    let start = Instant::now();
+
+    let mut child = Command::new("nargo")
+        .arg("test")
+        .current_dir(project_root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run `nargo test` in {:?}", project_root))?;
+
+    // Drain pipes on background threads so a chatty child cannot block on a full pipe.
+    let stdout_reader = child.stdout.take().map(spawn_reader);
+    let stderr_reader = child.stderr.take().map(spawn_reader);
+
+    let (status, timed_out) = loop {
+        if let Some(status) = child
+            .try_wait()
+            .context("failed to wait for `nargo test`")?
+        {
+            break (Some(status), false);
+        }
+
+        if start.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break (None, true);
+        }
+
+        thread::sleep(TIMEOUT_POLL_INTERVAL);
+    };
+
+    let duration = start.elapsed();
+    let stdout = stdout_reader.map(join_reader).unwrap_or_default();
+    let stderr = stderr_reader.map(join_reader).unwrap_or_default();
+
+    Ok(NargoTestResult {
+        exit_code: status.and_then(|s| s.code()),
+        success: status.is_some_and(|s| s.success()),
+        stdout,
+        stderr,
+        duration,
+        timed_out,
+    })
+}
+
+fn spawn_reader<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn join_reader(handle: thread::JoinHandle<Vec<u8>>) -> String {
+    let bytes = handle.join().unwrap_or_default();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+fn run_nargo_test_to_completion(project_root: &Path) -> Result<NargoTestResult> {
+    let start = Instant::now();
 
     let output = Command::new("nargo")
         .arg("test")
@@ -49,6 +181,7 @@ pub fn run_nargo_test(project_root: &Path) -> Result<NargoTestResult> {
         stdout,
         stderr,
         duration,
+        timed_out: false,
     })
 }
 
@@ -104,6 +237,42 @@ pub fn compiler_version_from_nargo_toml(project_root: &Path) -> Result<Option<St
     Ok(None)
 }
 
+/// Read `[package] name` from `Nargo.toml`, if present.
+pub fn package_name_from_nargo_toml(project_root: &Path) -> Result<Option<String>> {
+    let path = project_root.join("Nargo.toml");
+    let contents = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {:?}", path)),
+    };
+
+    let mut in_package = false;
+    for raw_line in contents.lines() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+
+        let Some((key, rhs)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "name" {
+            continue;
+        }
+
+        let v = rhs.trim().trim_matches('"').trim_matches('\'').trim();
+        if !v.is_empty() {
+            return Ok(Some(v.to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +290,27 @@ mod tests {
         dir
     }
 
+    #[test]
+    fn failed_tests_parses_nargo_progress_lines() {
+        let result = NargoTestResult {
+            exit_code: Some(1),
+            success: false,
+            stdout: [
+                "[pkg] Running 3 test functions",
+                "[pkg] Testing test_ok... ok",
+                "[pkg] Testing test_bad... \x1b[31mFAIL\x1b[0m",
+                "Testing other::test_range ... FAIL",
+                "[pkg] Testing test_bad... FAIL",
+            ]
+            .join("\n"),
+            stderr: "error: Testing is fun... FAILED to parse".to_string(),
+            duration: Duration::from_millis(1),
+            timed_out: false,
+        };
+
+        assert_eq!(result.failed_tests(), vec!["test_bad", "other::test_range"]);
+    }
+
     #[test]
     fn compiler_version_none_when_missing_file() {
         let dir = mk_temp_dir();
@@ -155,6 +345,21 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn package_name_reads_only_the_package_table() {
+        let dir = mk_temp_dir();
+        fs::write(
+            dir.join("Nargo.toml"),
+            "[dependencies]\nname = \"not_me\"\n\n[package]\nname = \"my_pkg\" # main\ntype = \"bin\"\n",
+        )
+        .unwrap();
+
+        let v = package_name_from_nargo_toml(&dir).unwrap();
+        assert_eq!(v.as_deref(), Some("my_pkg"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn compiler_version_errors_when_nargo_toml_is_a_directory() {
         let dir = mk_temp_dir();
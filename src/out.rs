@@ -4,10 +4,24 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use serde::Serialize;
 
+use crate::diff::mutant_diff;
 use crate::mutant::{Mutant, MutantOutcome};
 use crate::project::Project;
 use crate::report::format_mutant_with_location;
 use crate::run_report::MutationRunReport;
+use crate::span::SpanLocation;
+use crate::ui::Ui;
+
+/// Shape of `outcomes.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutcomesFormat {
+    /// zk-mutant's own compact outcome list.
+    #[default]
+    ZkMutant,
+
+    /// cargo-mutants' `outcomes.json` schema (scenarios with phase results).
+    CargoMutants,
+}
 
 /// Write `mutants.json` containing all discovered mutants (pre-limit).
 pub fn write_mutants_json(out_dir: &Path, mutants: &[Mutant]) -> Result<()> {
@@ -15,11 +29,18 @@ pub fn write_mutants_json(out_dir: &Path, mutants: &[Mutant]) -> Result<()> {
     write_pretty_json(&path, mutants)
 }
 
+/// Read a `mutants.json` written by [`write_mutants_json`].
+pub fn read_mutants_json(path: &Path) -> Result<Vec<Mutant>> {
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    serde_json::from_str(&text).with_context(|| format!("failed to parse {:?}", path))
+}
+
 /// Write `outcomes.json` as a compact list of outcomes for executed mutants.
 pub fn write_outcomes_json(out_dir: &Path, report: &MutationRunReport) -> Result<()> {
     #[derive(Debug, Serialize)]
     struct OutcomeEntry {
         id: u64,
+        fingerprint: String,
         file: PathBuf,
         start: u32,
         end: u32,
@@ -27,12 +48,14 @@ pub fn write_outcomes_json(out_dir: &Path, report: &MutationRunReport) -> Result
         name: String,
         outcome: MutantOutcome,
         duration_ms: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        location: Option<SpanLocation>,
     }
 
     #[derive(Debug, Serialize)]
     struct OutcomesFile {
-        tool: &'static str,
-        version: &'static str,
+        tool: String,
+        version: String,
         project_root: PathBuf,
         discovered: usize,
         executed: usize,
@@ -40,6 +63,8 @@ pub fn write_outcomes_json(out_dir: &Path, report: &MutationRunReport) -> Result
         summary: crate::run_report::RunSummary,
         mutants: Vec<OutcomeEntry>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        cache: Option<crate::run_report::CacheStats>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         error: Option<String>,
     }
 
@@ -48,6 +73,7 @@ pub fn write_outcomes_json(out_dir: &Path, report: &MutationRunReport) -> Result
         .iter()
         .map(|m| OutcomeEntry {
             id: m.id,
+            fingerprint: m.fingerprint.clone(),
             file: m.span.file.clone(),
             start: m.span.start,
             end: m.span.end,
@@ -55,20 +81,22 @@ pub fn write_outcomes_json(out_dir: &Path, report: &MutationRunReport) -> Result
             name: m.operator.name.clone(),
             outcome: m.outcome.clone(),
             duration_ms: m.duration_ms,
+            location: m.location.clone(),
         })
         .collect();
 
     entries.sort_by_key(|e| e.id);
 
     let file = OutcomesFile {
-        tool: report.tool,
-        version: report.version,
+        tool: report.tool.clone(),
+        version: report.version.clone(),
         project_root: report.project_root.clone(),
         discovered: report.discovered,
         executed: report.executed,
         baseline: report.baseline.clone(),
         summary: report.summary.clone(),
         mutants: entries,
+        cache: report.cache.clone(),
         error: report.error.clone(),
     };
 
@@ -80,6 +108,7 @@ pub fn write_outcomes_json(out_dir: &Path, report: &MutationRunReport) -> Result
 /// - caught.txt   (killed)
 /// - missed.txt   (survived)
 /// - unviable.txt (invalid)
+/// - timeout.txt  (timeout)
 pub fn write_outcome_txts(out_dir: &Path, project: &Project, mutants: &[Mutant]) -> Result<()> {
     write_txt_for(
         out_dir.join("caught.txt"),
@@ -99,11 +128,26 @@ pub fn write_outcome_txts(out_dir: &Path, project: &Project, mutants: &[Mutant])
         mutants,
         MutantOutcome::Invalid,
     )?;
+    write_txt_for(
+        out_dir.join("timeout.txt"),
+        project,
+        mutants,
+        MutantOutcome::Timeout,
+    )?;
     Ok(())
 }
 
-/// Write a minimal `diff/000001.diff` file per mutant (snippet-based).
-pub fn write_diff_dir(out_dir: &Path, mutants: &[Mutant]) -> Result<()> {
+/// Write `diff/000001.diff` per executed mutant: a unified diff with `context` lines,
+/// preceded by a one-line description (ignored by `git apply` and `patch -p1`).
+///
+/// Mutants whose source no longer matches are skipped with a warning.
+pub fn write_diff_dir(
+    out_dir: &Path,
+    project: &Project,
+    mutants: &[Mutant],
+    context: usize,
+    ui: &Ui,
+) -> Result<()> {
     let diff_dir = out_dir.join("diff");
     fs::create_dir_all(&diff_dir)
         .with_context(|| format!("failed to create diff dir {:?}", diff_dir))?;
@@ -117,16 +161,14 @@ pub fn write_diff_dir(out_dir: &Path, mutants: &[Mutant]) -> Result<()> {
             continue;
         }
 
-        let file = m.span.file.display().to_string();
-        let op = format!("{:?}/{}", m.operator.category, m.operator.name);
-
-        let content = format!(
-            "--- {file}\n+++ {file}\n@@ [{start}..{end}] {op}\n- {orig:?}\n+ {mutated:?}\n",
-            start = m.span.start,
-            end = m.span.end,
-            orig = m.original_snippet,
-            mutated = m.mutated_snippet,
-        );
+        let diff = match mutant_diff(project, m, context) {
+            Ok(diff) => diff,
+            Err(e) => {
+                ui.warn(format!("skipping diff for mutant {}: {e}", m.id));
+                continue;
+            }
+        };
+        let content = format!("{}\n{}", format_mutant_with_location(project, m), diff);
 
         let path = diff_dir.join(format!("{:06}.diff", m.id));
         fs::write(&path, content).with_context(|| format!("failed to write {:?}", path))?;
@@ -150,9 +192,19 @@ pub fn write_log(out_dir: &Path, report: &MutationRunReport) -> Result<()> {
         report.baseline.success, report.baseline.exit_code, report.baseline.duration_ms
     ));
     lines.push(format!(
-        "summary: killed={} survived={} invalid={}",
-        report.summary.killed, report.summary.survived, report.summary.invalid
+        "summary: killed={} survived={} invalid={} timeout={} score={}",
+        report.summary.killed,
+        report.summary.survived,
+        report.summary.invalid,
+        report.summary.timeout,
+        crate::report::score_label(report.summary.score)
     ));
+    if let Some(cache) = &report.cache {
+        lines.push(format!(
+            "cache: hits={} misses={}",
+            cache.hits, cache.misses
+        ));
+    }
     if let Some(err) = &report.error {
         lines.push(format!("error: {err}"));
     }
@@ -248,4 +300,22 @@ mod tests {
             "unviable.txt should list only invalid"
         );
     }
+
+    #[test]
+    fn diff_dir_skips_stale_mutants() {
+        let project = Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load");
+        let mut mutants = discover_mutants(&project);
+        for m in &mut mutants {
+            m.outcome = MutantOutcome::Killed;
+        }
+        mutants[0].original_snippet = "<stale>".to_string();
+
+        let td = TempDir::new().expect("TempDir should create");
+        write_diff_dir(td.path(), &project, &mutants, 3, &Ui::silent())
+            .expect("write_diff_dir should succeed");
+
+        assert!(!td.path().join("diff/000001.diff").exists());
+        assert!(td.path().join("diff/000002.diff").exists());
+    }
 }
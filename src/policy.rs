@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ThresholdConfig;
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::report::score_label;
+use crate::run_report::{RunSummary, mutation_score};
+
+/// Which CI policy a run violated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// Survivors with `--fail-on-survivors` (only new survivors when a baseline is given).
+    Survivors,
+
+    /// Overall mutation score below `--min-score` / `thresholds.min_score`.
+    MinScore,
+
+    /// Mutation score under a path prefix below its configured minimum.
+    PathMinScore,
+
+    /// Invalid mutants with `--fail-on-invalid`.
+    Invalid,
+
+    /// Timed-out mutants with `--fail-on-timeout`.
+    Timeout,
+}
+
+/// A failed threshold, with a human-readable explanation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PolicyViolation {
+    pub kind: ViolationKind,
+    pub message: String,
+}
+
+/// Effective thresholds for a run (CLI flags merged over the config file).
+#[derive(Debug, Clone, Default)]
+pub struct Thresholds {
+    pub min_score: Option<f64>,
+    pub paths: BTreeMap<String, f64>,
+    pub fail_on_invalid: bool,
+    pub fail_on_timeout: bool,
+}
+
+impl Thresholds {
+    /// Merge CLI flags over config values: `--min-score` replaces `min_score`,
+    /// boolean flags can only turn a check on.
+    pub fn new(
+        config: &ThresholdConfig,
+        min_score: Option<f64>,
+        fail_on_invalid: bool,
+        fail_on_timeout: bool,
+    ) -> Self {
+        Self {
+            min_score: min_score.or(config.min_score),
+            paths: config.paths.clone(),
+            fail_on_invalid: fail_on_invalid || config.fail_on_invalid,
+            fail_on_timeout: fail_on_timeout || config.fail_on_timeout,
+        }
+    }
+}
+
+/// Violation for survivors that fail `--fail-on-survivors`.
+///
+/// `known_baseline` selects the wording used when a `--survivors-baseline` is in effect.
+pub fn survivors_violation(failing_survivors: usize, known_baseline: bool) -> PolicyViolation {
+    let message = if known_baseline {
+        format!(
+            "{} new mutant(s) survived (not in --survivors-baseline)",
+            failing_survivors
+        )
+    } else {
+        format!(
+            "{} mutant(s) survived (--fail-on-survivors)",
+            failing_survivors
+        )
+    };
+
+    PolicyViolation {
+        kind: ViolationKind::Survivors,
+        message,
+    }
+}
+
+/// Check a finished run against the score, invalid and timeout thresholds.
+///
+/// Scores that are undefined (no killed or survived mutants) never fail a threshold.
+pub fn evaluate(
+    thresholds: &Thresholds,
+    summary: &RunSummary,
+    mutants: &[Mutant],
+) -> Vec<PolicyViolation> {
+    let mut out = Vec::new();
+
+    if let (Some(min), Some(score)) = (thresholds.min_score, summary.score) {
+        if score < min {
+            out.push(PolicyViolation {
+                kind: ViolationKind::MinScore,
+                message: format!(
+                    "mutation score {} is below the minimum {} (killed: {}, survived: {})",
+                    score_label(Some(score)),
+                    score_label(Some(min)),
+                    summary.killed,
+                    summary.survived
+                ),
+            });
+        }
+    }
+
+    for (prefix, min) in &thresholds.paths {
+        let (killed, survived) = counts_under(mutants, prefix);
+        let Some(score) = mutation_score(killed, survived) else {
+            continue;
+        };
+        if score < *min {
+            out.push(PolicyViolation {
+                kind: ViolationKind::PathMinScore,
+                message: format!(
+                    "mutation score {} in {} is below the minimum {} (killed: {}, survived: {})",
+                    score_label(Some(score)),
+                    prefix,
+                    score_label(Some(*min)),
+                    killed,
+                    survived
+                ),
+            });
+        }
+    }
+
+    if thresholds.fail_on_invalid && summary.invalid > 0 {
+        out.push(PolicyViolation {
+            kind: ViolationKind::Invalid,
+            message: format!(
+                "{} mutant(s) were invalid (--fail-on-invalid)",
+                summary.invalid
+            ),
+        });
+    }
+
+    if thresholds.fail_on_timeout && summary.timeout > 0 {
+        out.push(PolicyViolation {
+            kind: ViolationKind::Timeout,
+            message: format!(
+                "{} mutant(s) timed out (--fail-on-timeout)",
+                summary.timeout
+            ),
+        });
+    }
+
+    out
+}
+
+/// Whether `file` is `prefix` itself or lies inside the directory `prefix`.
+///
+/// Matches whole path components, so `src/merkle` covers `src/merkle/tree.nr` but not
+/// `src/merkle_old/tree.nr`.
+pub(crate) fn path_is_under(file: &Path, prefix: &str) -> bool {
+    file.starts_with(prefix)
+}
+
+/// Killed and survived counts for mutants whose file is under `prefix`.
+fn counts_under(mutants: &[Mutant], prefix: &str) -> (usize, usize) {
+    let mut killed = 0;
+    let mut survived = 0;
+
+    for m in mutants {
+        if !path_is_under(&m.span.file, prefix) {
+            continue;
+        }
+        match m.outcome {
+            MutantOutcome::Killed => killed += 1,
+            MutantOutcome::Survived => survived += 1,
+            _ => {}
+        }
+    }
+
+    (killed, survived)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn mutant(file: &str, outcome: MutantOutcome) -> Mutant {
+        Mutant {
+            outcome,
+            duration_ms: Some(1),
+            ..test_support::mutant(1, file, "eq_to_neq", "==", "!=")
+        }
+    }
+
+    fn summary_of(mutants: &[Mutant]) -> RunSummary {
+        let mut s = RunSummary::default();
+        for m in mutants {
+            match m.outcome {
+                MutantOutcome::Killed => s.killed += 1,
+                MutantOutcome::Survived => s.survived += 1,
+                MutantOutcome::Invalid => s.invalid += 1,
+                MutantOutcome::Timeout => s.timeout += 1,
+                MutantOutcome::NotRun => {}
+            }
+        }
+        s.score = mutation_score(s.killed, s.survived);
+        s
+    }
+
+    #[test]
+    fn cli_flags_override_config() {
+        let config = ThresholdConfig {
+            min_score: Some(50.0),
+            paths: BTreeMap::new(),
+            fail_on_invalid: true,
+            fail_on_timeout: false,
+        };
+
+        let t = Thresholds::new(&config, Some(90.0), false, true);
+        assert_eq!(t.min_score, Some(90.0));
+        assert!(t.fail_on_invalid);
+        assert!(t.fail_on_timeout);
+
+        let t = Thresholds::new(&config, None, false, false);
+        assert_eq!(t.min_score, Some(50.0));
+    }
+
+    #[test]
+    fn evaluate_reports_each_failed_threshold() {
+        let mutants = vec![
+            mutant("src/merkle/tree.nr", MutantOutcome::Killed),
+            mutant("src/merkle/tree.nr", MutantOutcome::Survived),
+            mutant("src/main.nr", MutantOutcome::Killed),
+            mutant("src/main.nr", MutantOutcome::Killed),
+            mutant("src/main.nr", MutantOutcome::Invalid),
+            mutant("src/main.nr", MutantOutcome::Timeout),
+        ];
+        let summary = summary_of(&mutants);
+
+        let thresholds = Thresholds {
+            min_score: Some(80.0),
+            paths: [
+                ("src/merkle/".to_string(), 85.0),
+                ("src/main.nr".to_string(), 85.0),
+                ("src/unused/".to_string(), 85.0),
+            ]
+            .into_iter()
+            .collect(),
+            fail_on_invalid: true,
+            fail_on_timeout: true,
+        };
+
+        let violations = evaluate(&thresholds, &summary, &mutants);
+        insta::assert_debug_snapshot!("policy_violations", violations);
+    }
+
+    #[test]
+    fn path_thresholds_match_whole_components() {
+        let mutants = vec![
+            mutant("src/merkle/tree.nr", MutantOutcome::Killed),
+            mutant("src/merkle_old/x.nr", MutantOutcome::Survived),
+        ];
+
+        assert_eq!(counts_under(&mutants, "src/merkle"), (1, 0));
+        assert_eq!(counts_under(&mutants, "src/merkle/"), (1, 0));
+        assert_eq!(counts_under(&mutants, "src/merkle_old/x.nr"), (0, 1));
+        assert_eq!(counts_under(&mutants, "src/merk"), (0, 0));
+    }
+
+    #[test]
+    fn evaluate_passes_when_thresholds_are_met() {
+        let mutants = vec![
+            mutant("src/main.nr", MutantOutcome::Killed),
+            mutant("src/main.nr", MutantOutcome::Invalid),
+        ];
+        let summary = summary_of(&mutants);
+
+        let thresholds = Thresholds {
+            min_score: Some(100.0),
+            ..Thresholds::default()
+        };
+
+        assert!(evaluate(&thresholds, &summary, &mutants).is_empty());
+    }
+}
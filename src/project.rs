@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::source::SourceFile;
+use crate::source_map::SourceMap;
 use anyhow::Result;
 use noir_metrics::{MetricsReport, analyze_path};
 
@@ -12,14 +13,24 @@ pub struct Project {
 
     /// Metrics report produced by noir-metrics.
     pub metrics: MetricsReport,
+
+    /// Contents of every source file, read once when the project is loaded.
+    sources: SourceMap,
 }
 
 impl Project {
-    /// Load a project and compute metrics for all `.nr` files under `root`.
+    /// Load a project, compute metrics for all `.nr` files under `root` and read them
+    /// into a [`SourceMap`].
     pub fn from_root(root: PathBuf) -> Result<Self> {
         // Delegate discovery + metrics to noir-metrics.
         let metrics = analyze_path(&root)?;
-        Ok(Self { root, metrics })
+        let mut project = Self {
+            root,
+            metrics,
+            sources: SourceMap::default(),
+        };
+        project.sources = SourceMap::load(&project.source_files());
+        Ok(project)
     }
 
     /// Root directory as a `Path`.
@@ -36,6 +47,11 @@ impl Project {
             .collect()
     }
 
+    /// Source files as read when the project was loaded.
+    pub fn sources(&self) -> &SourceMap {
+        &self.sources
+    }
+
     /// Look up a source file by its project-relative path (for example `src/main.nr`).
     pub fn find_source(&self, rel: &std::path::Path) -> Option<SourceFile> {
         self.metrics
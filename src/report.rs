@@ -1,5 +1,6 @@
 use crate::mutant::{Mutant, MutantOutcome};
 use crate::project::Project;
+use crate::run_report::{ScoreBreakdown, ScoreBucket};
 
 /// Print a detailed list of all mutants and their outcomes.
 ///
@@ -71,22 +72,71 @@ pub fn render_surviving_mutants(project: &Project, mutants: &[Mutant]) -> Vec<St
     out
 }
 
+/// Render mutation score breakdowns (worst first).
+///
+/// The per-file table is always included; per-function, per-operator and per-category
+/// tables only when `detailed` is set.
+pub fn render_score_breakdown(breakdown: &ScoreBreakdown, detailed: bool) -> Vec<String> {
+    let mut out = Vec::new();
+
+    let mut sections = vec![("file", &breakdown.by_file)];
+    if detailed {
+        sections.push(("function", &breakdown.by_function));
+        sections.push(("operator", &breakdown.by_operator));
+        sections.push(("category", &breakdown.by_category));
+    }
+
+    for (title, buckets) in sections {
+        if buckets.is_empty() {
+            continue;
+        }
+
+        out.push(format!("--- mutation score by {title} (worst first) ---"));
+        for b in buckets {
+            out.push(format_score_bucket(b));
+        }
+    }
+
+    out
+}
+
+/// Format a mutation score as a percentage, or `n/a` when undefined.
+pub fn score_label(score: Option<f64>) -> String {
+    match score {
+        Some(s) => format!("{s:.2}%"),
+        None => "n/a".to_string(),
+    }
+}
+
+fn format_score_bucket(b: &ScoreBucket) -> String {
+    format!(
+        "{:>8} {} (killed: {}, survived: {}, invalid: {}, timeout: {})",
+        score_label(b.score),
+        b.key,
+        b.killed,
+        b.survived,
+        b.invalid,
+        b.timeout
+    )
+}
+
 fn collect_sorted<'a>(iter: impl Iterator<Item = &'a Mutant>) -> Vec<&'a Mutant> {
     let mut v: Vec<&'a Mutant> = iter.collect();
     v.sort_by_key(|m| m.id);
     v
 }
 
-fn outcome_label(outcome: &MutantOutcome) -> &'static str {
+pub(crate) fn outcome_label(outcome: &MutantOutcome) -> &'static str {
     match outcome {
         MutantOutcome::NotRun => "not_run",
         MutantOutcome::Killed => "killed",
         MutantOutcome::Survived => "survived",
         MutantOutcome::Invalid => "invalid",
+        MutantOutcome::Timeout => "timeout",
     }
 }
 
-fn duration_label(duration_ms: Option<u64>) -> String {
+pub(crate) fn duration_label(duration_ms: Option<u64>) -> String {
     match duration_ms {
         Some(ms) => format!("{ms}ms"),
         None => "-".to_string(),
@@ -95,38 +145,44 @@ fn duration_label(duration_ms: Option<u64>) -> String {
 
 /// Format one mutant as a single, readable line using line/column positions when possible.
 ///
-/// Falls back to byte spans when the source file cannot be read.
+/// Uses the location recorded at discovery when present, otherwise looks the span up in
+/// the project's [`SourceMap`](crate::source_map::SourceMap); falls back to byte spans
+/// when neither is available.
 pub fn format_mutant_with_location(project: &Project, m: &Mutant) -> String {
-    let source = match project.find_source(&m.span.file) {
-        Some(s) => s,
-        None => return format_mutant_short(m),
-    };
+    if let Some(loc) = &m.location {
+        return format_mutant_at(
+            m,
+            (loc.start.line, loc.start.column),
+            (loc.end.line, loc.end.column),
+        );
+    }
 
-    let code = match source.read_to_string() {
-        Ok(c) => c,
-        Err(_) => return format_mutant_short(m),
+    let Some(text) = project.sources().get(&m.span.file) else {
+        return format_mutant_short(m);
     };
 
-    let start = m.span.start as usize;
-    let end = m.span.end as usize;
-
-    let Some((sl, sc)) = byte_offset_to_line_col(&code, start) else {
+    let Some((sl, sc)) = text.line_col(m.span.start as usize) else {
         return format_mutant_short(m);
     };
 
-    let Some((el, ec)) = byte_offset_to_line_col(&code, end) else {
+    let Some((el, ec)) = text.line_col(m.span.end as usize) else {
         return format_mutant_short(m);
     };
 
+    format_mutant_at(m, (sl, sc), (el, ec))
+}
+
+fn format_mutant_at(m: &Mutant, (sl, sc): (usize, usize), (el, ec): (usize, usize)) -> String {
     let file = m.span.file.display();
 
     format!(
-        "#{id} {file}:{sl}:{sc}-{el}:{ec} {category:?}/{name}: {orig:?} -> {mutated:?}",
+        "#{id} {file}:{sl}:{sc}-{el}:{ec} {category:?}/{name}: {orig:?} -> {mutated:?}{place}",
         id = m.id,
         category = m.operator.category,
         name = m.operator.name,
         orig = m.original_snippet,
         mutated = m.mutated_snippet,
+        place = function_suffix(m),
     )
 }
 
@@ -137,57 +193,38 @@ pub fn format_mutant_short(m: &Mutant) -> String {
     let end = m.span.end;
 
     format!(
-        "#{id} {file} [{start}..{end}] {category:?}/{name}: {orig:?} -> {mutated:?}",
+        "#{id} {file} [{start}..{end}] {category:?}/{name}: {orig:?} -> {mutated:?}{place}",
         id = m.id,
         category = m.operator.category,
         name = m.operator.name,
         orig = m.original_snippet,
         mutated = m.mutated_snippet,
+        place = function_suffix(m),
     )
 }
 
-/// Convert a byte offset into a 1-based (line, column) location.
-///
-/// Column counts Unicode scalar values on the line segment.
-fn byte_offset_to_line_col(code: &str, offset: usize) -> Option<(usize, usize)> {
-    if offset > code.len() {
-        return None;
+/// ` in <qualified function>` for mutants inside a function, otherwise empty.
+fn function_suffix(m: &Mutant) -> String {
+    match m.function_label() {
+        Some(f) => format!(" in {f}"),
+        None => String::new(),
     }
-
-    let prefix = &code[..offset];
-
-    let line = prefix.as_bytes().iter().filter(|&&b| b == b'\n').count() + 1;
-    let line_start = prefix.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
-    let col = code[line_start..offset].chars().count() + 1;
-
-    Some((line, col))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mutant::{MutationOperator, OperatorCategory};
     use crate::project::Project;
-    use crate::span::SourceSpan;
+    use crate::test_support::{mutant, span};
     use std::path::PathBuf;
 
     #[test]
     fn format_short_is_stable() {
         let m = Mutant {
-            id: 7,
-            operator: MutationOperator {
-                category: OperatorCategory::Condition,
-                name: "eq_to_neq".to_string(),
-            },
-            span: SourceSpan {
-                file: PathBuf::from("src/main.nr"),
-                start: 12,
-                end: 14,
-            },
-            original_snippet: "==".to_string(),
-            mutated_snippet: "!=".to_string(),
+            span: span("src/main.nr", 12, 14),
             outcome: MutantOutcome::Survived,
             duration_ms: Some(123),
+            ..mutant(7, "src/main.nr", "eq_to_neq", "==", "!=")
         };
 
         insta::assert_debug_snapshot!("format_mutant_short", format_mutant_short(&m));
@@ -200,36 +237,14 @@ mod tests {
 
         let mutants = vec![
             Mutant {
-                id: 2,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "eq_to_neq".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/utils.nr"),
-                    start: 0,
-                    end: 2,
-                },
-                original_snippet: "==".to_string(),
-                mutated_snippet: "!=".to_string(),
                 outcome: MutantOutcome::Survived,
                 duration_ms: Some(20),
+                ..mutant(2, "src/utils.nr", "eq_to_neq", "==", "!=")
             },
             Mutant {
-                id: 1,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "lt_to_ge".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/main.nr"),
-                    start: 0,
-                    end: 1,
-                },
-                original_snippet: "<".to_string(),
-                mutated_snippet: ">=".to_string(),
                 outcome: MutantOutcome::Killed,
                 duration_ms: Some(10),
+                ..mutant(1, "src/main.nr", "lt_to_ge", "<", ">=")
             },
         ];
 
@@ -246,36 +261,14 @@ mod tests {
 
         let mutants = vec![
             Mutant {
-                id: 1,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "lt_to_ge".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/main.nr"),
-                    start: 0,
-                    end: 1,
-                },
-                original_snippet: "<".to_string(),
-                mutated_snippet: ">=".to_string(),
                 outcome: MutantOutcome::Killed,
                 duration_ms: Some(10),
+                ..mutant(1, "src/main.nr", "lt_to_ge", "<", ">=")
             },
             Mutant {
-                id: 2,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "eq_to_neq".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/utils.nr"),
-                    start: 0,
-                    end: 2,
-                },
-                original_snippet: "==".to_string(),
-                mutated_snippet: "!=".to_string(),
                 outcome: MutantOutcome::Survived,
                 duration_ms: Some(20),
+                ..mutant(2, "src/utils.nr", "eq_to_neq", "==", "!=")
             },
         ];
 
@@ -286,15 +279,26 @@ mod tests {
     }
 
     #[test]
-    fn byte_offset_to_line_col_basic() {
-        let code = "a\nbcd\nef";
-        assert_eq!(byte_offset_to_line_col(code, 0), Some((1, 1)));
-        assert_eq!(byte_offset_to_line_col(code, 1), Some((1, 2)));
-        assert_eq!(byte_offset_to_line_col(code, 2), Some((2, 1)));
-        assert_eq!(byte_offset_to_line_col(code, 4), Some((2, 3)));
-        assert_eq!(byte_offset_to_line_col(code, 6), Some((3, 1)));
-        assert_eq!(byte_offset_to_line_col(code, code.len()), Some((3, 3)));
-        assert_eq!(byte_offset_to_line_col(code, code.len() + 1), None);
+    fn render_score_breakdown_detailed_snapshot() {
+        let mk = |file: &str, function: &str, outcome: MutantOutcome| Mutant {
+            function: Some(function.to_string()),
+            outcome,
+            duration_ms: Some(1),
+            ..mutant(0, file, "eq_to_neq", "==", "!=")
+        };
+
+        let mutants = vec![
+            mk("src/main.nr", "main", MutantOutcome::Killed),
+            mk("src/utils.nr", "check_addition", MutantOutcome::Survived),
+            mk("src/utils.nr", "check_addition", MutantOutcome::Killed),
+        ];
+        let breakdown = ScoreBreakdown::from_mutants(&mutants);
+
+        assert_eq!(render_score_breakdown(&breakdown, false).len(), 3);
+        insta::assert_debug_snapshot!(
+            "render_score_breakdown_detailed",
+            render_score_breakdown(&breakdown, true)
+        );
     }
 
     #[test]
@@ -303,36 +307,14 @@ mod tests {
         let project = Project::from_root(root).expect("Project::from_root should succeed");
         let mutants = vec![
             Mutant {
-                id: 1,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "lt_to_ge".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/main.nr"),
-                    start: 0,
-                    end: 1,
-                },
-                original_snippet: "<".to_string(),
-                mutated_snippet: ">=".to_string(),
                 outcome: MutantOutcome::Killed,
                 duration_ms: Some(10),
+                ..mutant(1, "src/main.nr", "lt_to_ge", "<", ">=")
             },
             Mutant {
-                id: 2,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "eq_to_neq".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/utils.nr"),
-                    start: 0,
-                    end: 2,
-                },
-                original_snippet: "==".to_string(),
-                mutated_snippet: "!=".to_string(),
                 outcome: MutantOutcome::Survived,
                 duration_ms: Some(20),
+                ..mutant(2, "src/utils.nr", "eq_to_neq", "==", "!=")
             },
         ];
         insta::assert_debug_snapshot!(
@@ -1,12 +1,16 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::mutant::Mutant;
+use crate::known_survivors::KnownSurvivorsSummary;
+use crate::mutant::{Mutant, MutantOutcome};
 use crate::nargo::NargoTestResult;
+use crate::policy::PolicyViolation;
+use crate::source_map::UnreadableSource;
 
 /// Summary counts for a mutation-testing run.
-#[derive(Debug, Default, Clone, Serialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct RunSummary {
     /// Number of mutants whose tests failed under mutation.
     pub killed: usize,
@@ -16,10 +20,134 @@ pub struct RunSummary {
 
     /// Number of mutants that could not be built or executed.
     pub invalid: usize,
+
+    /// Number of mutants whose tests exceeded `--timeout`.
+    #[serde(default)]
+    pub timeout: usize,
+
+    /// Mutation score in percent (see [`mutation_score`]); `None` when undefined.
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+impl RunSummary {
+    /// Outcome counts and score for a set of mutants (not-run mutants are ignored).
+    pub fn from_mutants(mutants: &[Mutant]) -> Self {
+        let mut s = Self::default();
+        for m in mutants {
+            match m.outcome {
+                MutantOutcome::Killed => s.killed += 1,
+                MutantOutcome::Survived => s.survived += 1,
+                MutantOutcome::Invalid => s.invalid += 1,
+                MutantOutcome::Timeout => s.timeout += 1,
+                MutantOutcome::NotRun => {}
+            }
+        }
+        s.score = mutation_score(s.killed, s.survived);
+        s
+    }
+}
+
+/// Outcome counts and score for one group of mutants (a file, function, operator, ...).
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreBucket {
+    /// Group key, for example `src/utils.nr` or `eq_to_neq`.
+    pub key: String,
+    pub killed: usize,
+    pub survived: usize,
+    pub invalid: usize,
+    #[serde(default)]
+    pub timeout: usize,
+
+    /// Mutation score in percent; `None` when undefined.
+    pub score: Option<f64>,
+}
+
+/// Mutation score breakdowns for executed mutants.
+///
+/// Every list is ordered worst-first: lowest score, then most survivors, then key.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub by_file: Vec<ScoreBucket>,
+
+    /// Keyed by `<file>:<qualified function>` (`<top-level>` outside functions).
+    pub by_function: Vec<ScoreBucket>,
+    pub by_operator: Vec<ScoreBucket>,
+    pub by_category: Vec<ScoreBucket>,
+}
+
+impl ScoreBreakdown {
+    /// Group executed mutants (not-run mutants are ignored).
+    pub fn from_mutants(mutants: &[Mutant]) -> Self {
+        let executed: Vec<&Mutant> = mutants
+            .iter()
+            .filter(|m| m.outcome != MutantOutcome::NotRun)
+            .collect();
+
+        Self {
+            by_file: buckets(&executed, |m| m.span.file.display().to_string()),
+            by_function: buckets(&executed, |m| {
+                format!(
+                    "{}:{}",
+                    m.span.file.display(),
+                    m.function_label().unwrap_or("<top-level>")
+                )
+            }),
+            by_operator: buckets(&executed, |m| m.operator.name.clone()),
+            by_category: buckets(&executed, |m| format!("{:?}", m.operator.category)),
+        }
+    }
+}
+
+fn buckets(mutants: &[&Mutant], key: impl Fn(&Mutant) -> String) -> Vec<ScoreBucket> {
+    let mut map: BTreeMap<String, ScoreBucket> = BTreeMap::new();
+
+    for m in mutants {
+        let k = key(m);
+        let b = map.entry(k.clone()).or_insert_with(|| ScoreBucket {
+            key: k,
+            ..ScoreBucket::default()
+        });
+        match m.outcome {
+            MutantOutcome::Killed => b.killed += 1,
+            MutantOutcome::Survived => b.survived += 1,
+            MutantOutcome::Invalid => b.invalid += 1,
+            MutantOutcome::Timeout => b.timeout += 1,
+            MutantOutcome::NotRun => {}
+        }
+    }
+
+    let mut out: Vec<ScoreBucket> = map
+        .into_values()
+        .map(|mut b| {
+            b.score = mutation_score(b.killed, b.survived);
+            b
+        })
+        .collect();
+
+    out.sort_by(|a, b| {
+        let sa = a.score.unwrap_or(f64::INFINITY);
+        let sb = b.score.unwrap_or(f64::INFINITY);
+        sa.total_cmp(&sb)
+            .then_with(|| b.survived.cmp(&a.survived))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+
+    out
+}
+
+/// Result cache hit/miss counts for a mutation-testing run.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Mutants whose outcome was reused from the cache.
+    pub hits: usize,
+
+    /// Mutants that had to be executed.
+    pub misses: usize,
 }
 
 /// Baseline `nargo test` metadata.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaselineReport {
     pub success: bool,
     pub exit_code: Option<i32>,
@@ -38,14 +166,15 @@ impl BaselineReport {
 
 /// Machine-readable report for a mutation test run.
 ///
-/// In `--json` mode we print this to stdout as pretty JSON.
-#[derive(Debug, Serialize)]
+/// In `--json` mode we print this to stdout as pretty JSON. It is also persisted as
+/// `run.json` and read back by `compare`.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MutationRunReport {
     /// Tool name, stable across versions.
-    pub tool: &'static str,
+    pub tool: String,
 
-    /// Current crate version.
-    pub version: &'static str,
+    /// Crate version that produced the report.
+    pub version: String,
 
     /// The project root used for this run.
     pub project_root: PathBuf,
@@ -62,47 +191,184 @@ pub struct MutationRunReport {
     /// Summary of mutant outcomes.
     pub summary: RunSummary,
 
+    /// Mutation score per file, function, operator and category.
+    #[serde(default)]
+    pub breakdown: ScoreBreakdown,
+
     /// Mutants with updated outcomes.
     pub mutants: Vec<Mutant>,
 
+    /// Result cache statistics (absent when the cache is disabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheStats>,
+
+    /// Comparison against the `--survivors-baseline` file, when one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_survivors: Option<KnownSurvivorsSummary>,
+
+    /// Thresholds this run failed (see `--min-score`, `--fail-on-*`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_violations: Vec<PolicyViolation>,
+
+    /// Source files that could not be read and therefore got no mutants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unreadable_sources: Vec<UnreadableSource>,
+
     /// Optional high-level error message (for example baseline failure).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
+/// Mutation score in percent: `killed / (killed + survived) * 100`.
+///
+/// Invalid, timed-out and not-run mutants are excluded. Returns `None` when nothing was killed or
+/// survived (the score is undefined).
+pub fn mutation_score(killed: usize, survived: usize) -> Option<f64> {
+    let total = killed + survived;
+    if total == 0 {
+        None
+    } else {
+        Some(killed as f64 / total as f64 * 100.0)
+    }
+}
+
 impl MutationRunReport {
+    /// Keep only the mutants matching `keep`; `executed`, the summary and the breakdowns
+    /// are recomputed from what remains.
+    ///
+    /// Known-survivor and policy results describe the whole run and cannot be recomputed
+    /// without the baseline file and thresholds, so they are dropped.
+    pub fn retain_mutants(&mut self, keep: impl Fn(&Mutant) -> bool) {
+        self.mutants.retain(|m| keep(m));
+        self.executed = self
+            .mutants
+            .iter()
+            .filter(|m| m.outcome != MutantOutcome::NotRun)
+            .count();
+        self.summary = RunSummary::from_mutants(&self.mutants);
+        self.breakdown = ScoreBreakdown::from_mutants(&self.mutants);
+        self.known_survivors = None;
+        self.policy_violations.clear();
+    }
+
     pub fn success(
         project_root: PathBuf,
         discovered: usize,
         executed: usize,
         baseline: BaselineReport,
-        summary: RunSummary,
+        mut summary: RunSummary,
         mutants: Vec<Mutant>,
     ) -> Self {
+        summary.score = mutation_score(summary.killed, summary.survived);
+        let breakdown = ScoreBreakdown::from_mutants(&mutants);
+
         Self {
-            tool: "zk-mutant",
-            version: env!("CARGO_PKG_VERSION"),
+            tool: "zk-mutant".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
             project_root,
             discovered,
             executed,
             baseline,
             summary,
+            breakdown,
             mutants,
+            cache: None,
+            known_survivors: None,
+            policy_violations: Vec::new(),
+            unreadable_sources: Vec::new(),
             error: None,
         }
     }
 
     pub fn failure(project_root: PathBuf, baseline: BaselineReport, error: String) -> Self {
         Self {
-            tool: "zk-mutant",
-            version: env!("CARGO_PKG_VERSION"),
+            tool: "zk-mutant".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
             project_root,
             discovered: 0,
             executed: 0,
             baseline,
             summary: RunSummary::default(),
+            breakdown: ScoreBreakdown::default(),
             mutants: Vec::new(),
+            cache: None,
+            known_survivors: None,
+            policy_violations: Vec::new(),
+            unreadable_sources: Vec::new(),
             error: Some(error),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn mutation_score_excludes_undefined_runs() {
+        assert_eq!(mutation_score(0, 0), None);
+        assert_eq!(mutation_score(3, 1), Some(75.0));
+        assert_eq!(mutation_score(0, 2), Some(0.0));
+        assert_eq!(mutation_score(2, 0), Some(100.0));
+    }
+
+    fn mutant(file: &str, function: Option<&str>, op: &str, outcome: MutantOutcome) -> Mutant {
+        Mutant {
+            function: function.map(str::to_string),
+            outcome,
+            ..test_support::mutant(0, file, op, "<", ">=")
+        }
+    }
+
+    #[test]
+    fn breakdown_orders_worst_first_and_ignores_not_run() {
+        let mutants = vec![
+            mutant("src/a.nr", Some("f"), "lt_to_ge", MutantOutcome::Killed),
+            mutant("src/a.nr", Some("f"), "eq_to_neq", MutantOutcome::Survived),
+            mutant("src/b.nr", None, "eq_to_neq", MutantOutcome::Survived),
+            mutant("src/c.nr", Some("g"), "eq_to_neq", MutantOutcome::Invalid),
+            mutant("src/d.nr", Some("h"), "eq_to_neq", MutantOutcome::NotRun),
+        ];
+
+        let b = ScoreBreakdown::from_mutants(&mutants);
+
+        insta::assert_debug_snapshot!("score_breakdown", b);
+    }
+
+    #[test]
+    fn retain_mutants_recomputes_summary_and_breakdown() {
+        let mut report = MutationRunReport::success(
+            PathBuf::from("."),
+            4,
+            4,
+            BaselineReport {
+                success: true,
+                exit_code: Some(0),
+                duration_ms: 0,
+            },
+            RunSummary::default(),
+            vec![
+                mutant("src/a.nr", Some("f"), "lt_to_ge", MutantOutcome::Killed),
+                mutant("src/a.nr", Some("f"), "eq_to_neq", MutantOutcome::Survived),
+                mutant("src/b.nr", None, "eq_to_neq", MutantOutcome::Survived),
+                mutant("src/b.nr", None, "eq_to_neq", MutantOutcome::Invalid),
+            ],
+        );
+
+        report.policy_violations = vec![PolicyViolation {
+            kind: crate::policy::ViolationKind::MinScore,
+            message: "mutation score 25.00% is below the minimum 80.00%".to_string(),
+        }];
+
+        report.retain_mutants(|m| m.span.file == std::path::Path::new("src/a.nr"));
+
+        assert!(report.policy_violations.is_empty());
+        assert_eq!(report.executed, 2);
+        assert_eq!(report.summary.killed, 1);
+        assert_eq!(report.summary.survived, 1);
+        assert_eq!(report.summary.invalid, 0);
+        assert_eq!(report.summary.score, Some(50.0));
+        assert_eq!(report.breakdown.by_file.len(), 1);
+    }
+}
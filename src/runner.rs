@@ -1,13 +1,15 @@
-use std::{fs, path::Path};
+use std::{fs, path::Path, time::Duration};
 
 use anyhow::{Context, Result};
 use tempfile::TempDir;
 
+use crate::cache::ResultCache;
 use crate::mutant::{Mutant, MutantOutcome};
-use crate::nargo::{NargoTestResult, run_nargo_test};
+use crate::mutant_dir::{LogCapture, write_cached_log, write_error_log, write_nargo_log};
+use crate::nargo::{NargoTestResult, run_nargo_test_with_timeout};
 use crate::patch::apply_checked_patch;
 use crate::project::Project;
-use crate::run_report::RunSummary;
+use crate::run_report::{RunSummary, mutation_score};
 use crate::ui::Ui;
 
 /// Copy the entire Noir project into a fresh temporary directory.
@@ -42,8 +44,16 @@ pub fn apply_mutant_in_temp_tree(temp_root: &Path, mutant: &Mutant) -> Result<()
         )
     })?;
 
+    write_mutant_in_temp_tree(temp_root, mutant, &original)
+}
+
+/// Write `original` with the mutant's span patch applied to the mutant's file inside a
+/// temporary project tree.
+fn write_mutant_in_temp_tree(temp_root: &Path, mutant: &Mutant, original: &str) -> Result<()> {
+    let temp_file_path = temp_root.join(&mutant.span.file);
+
     let mutated = apply_checked_patch(
-        &original,
+        original,
         &mutant.span,
         &mutant.original_snippet,
         &mutant.mutated_snippet,
@@ -59,37 +69,85 @@ pub fn apply_mutant_in_temp_tree(temp_root: &Path, mutant: &Mutant) -> Result<()
     Ok(())
 }
 
+/// Per-run settings for executing mutants.
+#[derive(Debug, Clone, Default)]
+pub struct RunnerOptions {
+    /// Kill `nargo test` for a mutant after this long and record a timeout.
+    pub timeout: Option<Duration>,
+
+    /// Write each mutant's `nargo test` output to its directory under the output dir.
+    pub logs: Option<LogCapture>,
+}
+
 /// Run `nargo test` on a temporary copy of the project with a single mutant applied.
-pub fn run_single_mutant_in_temp(project: &Project, mutant: &Mutant) -> Result<NargoTestResult> {
+pub fn run_single_mutant_in_temp(
+    project: &Project,
+    mutant: &Mutant,
+    options: &RunnerOptions,
+) -> Result<NargoTestResult> {
     let temp = copy_project_to_temp(project)?;
     let temp_root = temp.path();
 
-    apply_mutant_in_temp_tree(temp_root, mutant)?;
-    let result = run_nargo_test(temp_root)?;
+    // The pristine file is already in memory; only the copy on disk needs rewriting.
+    match project.sources().code(&mutant.span.file) {
+        Some(original) => write_mutant_in_temp_tree(temp_root, mutant, original)?,
+        None => apply_mutant_in_temp_tree(temp_root, mutant)?,
+    }
+    let result = run_nargo_test_with_timeout(temp_root, options.timeout)?;
 
     Ok(result)
 }
 
 /// Naive driver: run all mutants, copying the project for each one.
+///
+/// When a `cache` is given, mutants with a cached outcome are not executed.
 pub fn run_all_mutants_in_temp(
     project: &Project,
     mutants: &mut [Mutant],
+    options: &RunnerOptions,
+    cache: Option<&mut ResultCache>,
     ui: &mut Ui,
 ) -> Result<RunSummary> {
-    run_all_mutants_with(project, mutants, run_single_mutant_in_temp, ui)
+    run_all_mutants_with(
+        project,
+        mutants,
+        run_single_mutant_in_temp,
+        options,
+        cache,
+        ui,
+    )
 }
 
 /// Run all mutants using the provided per-mutant runner.
 fn run_all_mutants_with(
     project: &Project,
     mutants: &mut [Mutant],
-    run_one: fn(&Project, &Mutant) -> Result<NargoTestResult>,
+    run_one: fn(&Project, &Mutant, &RunnerOptions) -> Result<NargoTestResult>,
+    options: &RunnerOptions,
+    mut cache: Option<&mut ResultCache>,
     ui: &mut Ui,
 ) -> Result<RunSummary> {
     let mut summary = RunSummary::default();
 
     for m in mutants.iter_mut() {
-        let result = match run_one(project, m) {
+        let cache_key = match cache.as_deref_mut() {
+            Some(c) => {
+                let key = c.key_for(project, m);
+                if let Some(entry) = c.lookup(key.as_deref()) {
+                    m.outcome = entry.outcome;
+                    m.duration_ms = entry.duration_ms;
+                    m.killed_by = entry.killed_by;
+                    record_outcome(&mut summary, &m.outcome);
+                    capture_log(options, ui, |c| write_cached_log(c, project, m));
+                    ui.mutant_progress(m);
+                    continue;
+                }
+                key
+            }
+            None => None,
+        };
+
+        let result = match run_one(project, m, options) {
             Ok(r) => r,
             Err(e) => {
                 ui.runner_error(format!(
@@ -98,26 +156,59 @@ fn run_all_mutants_with(
                 ));
                 m.outcome = MutantOutcome::Invalid;
                 summary.invalid += 1;
+                capture_log(options, ui, |c| {
+                    write_error_log(c, project, m, &format!("{e:#}"))
+                });
                 continue;
             }
         };
 
         m.duration_ms = Some(result.duration.as_millis() as u64);
 
-        if result.success {
-            m.outcome = MutantOutcome::Survived;
-            summary.survived += 1;
+        m.outcome = if result.timed_out {
+            MutantOutcome::Timeout
+        } else if result.success {
+            MutantOutcome::Survived
         } else {
-            m.outcome = MutantOutcome::Killed;
-            summary.killed += 1;
+            MutantOutcome::Killed
+        };
+        if m.outcome == MutantOutcome::Killed {
+            m.killed_by = result.failed_tests();
+        }
+        record_outcome(&mut summary, &m.outcome);
+        capture_log(options, ui, |c| write_nargo_log(c, project, m, &result));
+
+        if let (Some(c), Some(key)) = (cache.as_deref_mut(), cache_key) {
+            c.insert(key, m);
         }
 
         ui.mutant_progress(m);
     }
 
+    summary.score = mutation_score(summary.killed, summary.survived);
+
     Ok(summary)
 }
 
+/// Write a mutant log when capture is enabled; failures only warn.
+fn capture_log(options: &RunnerOptions, ui: &Ui, write: impl FnOnce(&LogCapture) -> Result<()>) {
+    if let Some(capture) = &options.logs {
+        if let Err(e) = write(capture) {
+            ui.warn(format!("failed to write mutant log: {e}"));
+        }
+    }
+}
+
+fn record_outcome(summary: &mut RunSummary, outcome: &MutantOutcome) {
+    match outcome {
+        MutantOutcome::Killed => summary.killed += 1,
+        MutantOutcome::Survived => summary.survived += 1,
+        MutantOutcome::Invalid => summary.invalid += 1,
+        MutantOutcome::Timeout => summary.timeout += 1,
+        MutantOutcome::NotRun => {}
+    }
+}
+
 /// Recursively copy all files and directories from `src` into `dst`.
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     fs::create_dir_all(dst).with_context(|| format!("failed to create dir {:?}", dst))?;
@@ -142,20 +233,17 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
 mod tests {
     use super::*;
     use crate::discover::discover_mutants;
-    use crate::mutant::{MutationOperator, OperatorCategory};
-    use crate::span::SourceSpan;
+    use crate::test_support::{mutant, span};
     use std::path::PathBuf;
     use std::time::Duration;
 
     fn apply_mutant_in_memory(project: &Project, mutant: &Mutant) -> anyhow::Result<String> {
-        let source = project.find_source(&mutant.span.file).ok_or_else(|| {
+        let original = project.sources().code(&mutant.span.file).ok_or_else(|| {
             anyhow::anyhow!("source file {:?} not part of project", mutant.span.file)
         })?;
 
-        let original = source.read_to_string()?;
-
         Ok(apply_checked_patch(
-            &original,
+            original,
             &mutant.span,
             &mutant.original_snippet,
             &mutant.mutated_snippet,
@@ -239,64 +327,27 @@ mod tests {
         let project = Project::from_root(root).expect("Project::from_root should succeed");
 
         let mut mutants = vec![
+            mutant(1, "src/main.nr", "lt_to_ge", "<", ">="),
+            mutant(2, "src/utils.nr", "eq_to_neq", "==", "!="),
             Mutant {
-                id: 1,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "lt_to_ge".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/main.nr"),
-                    start: 0,
-                    end: 1,
-                },
-                original_snippet: "<".to_string(),
-                mutated_snippet: ">=".to_string(),
-                outcome: MutantOutcome::NotRun,
-                duration_ms: None,
-            },
-            Mutant {
-                id: 2,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "eq_to_neq".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/utils.nr"),
-                    start: 0,
-                    end: 2,
-                },
-                original_snippet: "==".to_string(),
-                mutated_snippet: "!=".to_string(),
-                outcome: MutantOutcome::NotRun,
-                duration_ms: None,
-            },
-            Mutant {
-                id: 3,
-                operator: MutationOperator {
-                    category: OperatorCategory::Condition,
-                    name: "neq_to_eq".to_string(),
-                },
-                span: SourceSpan {
-                    file: PathBuf::from("src/main.nr"),
-                    start: 10,
-                    end: 12,
-                },
-                original_snippet: "!=".to_string(),
-                mutated_snippet: "==".to_string(),
-                outcome: MutantOutcome::NotRun,
-                duration_ms: None,
+                span: span("src/main.nr", 10, 12),
+                ..mutant(3, "src/main.nr", "neq_to_eq", "!=", "==")
             },
         ];
 
-        fn fake_run_one(_project: &Project, m: &Mutant) -> Result<NargoTestResult> {
+        fn fake_run_one(
+            _project: &Project,
+            m: &Mutant,
+            _options: &RunnerOptions,
+        ) -> Result<NargoTestResult> {
             match m.id {
                 1 => Ok(NargoTestResult {
                     exit_code: Some(1),
                     success: false,
-                    stdout: String::new(),
+                    stdout: "[simple_noir] Testing test_main... FAIL\n".to_string(),
                     stderr: String::new(),
                     duration: Duration::from_millis(10),
+                    timed_out: false,
                 }),
                 2 => Ok(NargoTestResult {
                     exit_code: Some(0),
@@ -304,6 +355,7 @@ mod tests {
                     stdout: String::new(),
                     stderr: String::new(),
                     duration: Duration::from_millis(20),
+                    timed_out: false,
                 }),
                 3 => Err(anyhow::anyhow!("simulated failure")),
                 _ => unreachable!(),
@@ -311,9 +363,62 @@ mod tests {
         }
 
         let mut ui = Ui::silent();
-        let summary = run_all_mutants_with(&project, &mut mutants, fake_run_one, &mut ui).unwrap();
+        let summary = run_all_mutants_with(
+            &project,
+            &mut mutants,
+            fake_run_one,
+            &RunnerOptions::default(),
+            None,
+            &mut ui,
+        )
+        .unwrap();
 
         insta::assert_debug_snapshot!("run_all_mutants_summary", summary);
         insta::assert_debug_snapshot!("run_all_mutants_mutants", mutants);
     }
+
+    #[test]
+    fn cached_outcomes_skip_execution() {
+        let root = PathBuf::from("tests/fixtures/simple_noir");
+        let project = Project::from_root(root).expect("Project::from_root should succeed");
+
+        let td = tempfile::TempDir::new().unwrap();
+        let cache_path = td.path().join("cache.json");
+
+        let mut seeded = discover_mutants(&project);
+        let mut cache = ResultCache::load(&cache_path, &project, None).unwrap();
+        for m in seeded.iter_mut() {
+            m.outcome = MutantOutcome::Killed;
+            m.duration_ms = Some(7);
+            let key = cache.key_for(&project, m).unwrap();
+            cache.insert(key, m);
+        }
+        cache.save().unwrap();
+
+        fn must_not_run(
+            _project: &Project,
+            m: &Mutant,
+            _options: &RunnerOptions,
+        ) -> Result<NargoTestResult> {
+            panic!("mutant {} should have been served from the cache", m.id);
+        }
+
+        let mut cache = ResultCache::load(&cache_path, &project, None).unwrap();
+        let mut mutants = discover_mutants(&project);
+        let mut ui = Ui::silent();
+        let summary = run_all_mutants_with(
+            &project,
+            &mut mutants,
+            must_not_run,
+            &RunnerOptions::default(),
+            Some(&mut cache),
+            &mut ui,
+        )
+        .unwrap();
+
+        assert_eq!(summary.killed, mutants.len());
+        assert!(mutants.iter().all(|m| m.duration_ms == Some(7)));
+        assert_eq!(cache.stats().hits, mutants.len());
+        assert_eq!(cache.stats().misses, 0);
+    }
 }
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::run_report::MutationRunReport;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Base ID that artifact URIs are relative to (resolved via `originalUriBaseIds`).
+const PROJECT_ROOT_BASE_ID: &str = "PROJECTROOT";
+
+/// Write surviving mutants as a SARIF 2.1.0 log.
+pub fn write_sarif(path: &Path, project: &Project, report: &MutationRunReport) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).with_context(|| format!("failed to create {:?}", parent))?;
+        }
+    }
+
+    let sarif = render_sarif(project, report);
+    let json = serde_json::to_string_pretty(&sarif).context("serialize SARIF")?;
+    fs::write(path, json).with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// Build the SARIF log: one rule per mutation operator, one result per survivor.
+///
+/// Regions carry both line/column (code points, 1-based) and byte offsets. Results are
+/// keyed by the mutant fingerprint so consumers can track them across runs.
+pub fn render_sarif(project: &Project, report: &MutationRunReport) -> Value {
+    let mut operators: BTreeMap<&str, &Mutant> = BTreeMap::new();
+    for m in &report.mutants {
+        operators.entry(m.operator.name.as_str()).or_insert(m);
+    }
+    let rule_index: BTreeMap<&str, usize> = operators
+        .keys()
+        .enumerate()
+        .map(|(i, name)| (*name, i))
+        .collect();
+
+    let rules: Vec<Value> = operators
+        .values()
+        .map(|m| {
+            json!({
+                "id": rule_id(m),
+                "name": m.operator.name,
+                "shortDescription": {
+                    "text": format!(
+                        "Surviving `{}` mutant ({:?})",
+                        m.operator.name, m.operator.category
+                    ),
+                },
+                "fullDescription": {
+                    "text": "A source-level mutation that no `nargo test` test detected. \
+                             Add or strengthen a test so the mutated circuit fails.",
+                },
+                "defaultConfiguration": { "level": "warning" },
+                "properties": { "category": format!("{:?}", m.operator.category) },
+            })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+
+    let mut survivors: Vec<&Mutant> = report
+        .mutants
+        .iter()
+        .filter(|m| m.outcome == MutantOutcome::Survived)
+        .collect();
+    survivors.sort_by_key(|m| m.id);
+
+    for m in survivors {
+        let mut region = json!({
+            "byteOffset": m.span.start,
+            "byteLength": m.span.end.saturating_sub(m.span.start),
+            "snippet": { "text": m.original_snippet },
+        });
+        if let Some(text) = project.sources().get(&m.span.file) {
+            if let (Some((sl, sc)), Some((el, ec))) = (
+                text.line_col(m.span.start as usize),
+                text.line_col(m.span.end as usize),
+            ) {
+                region["startLine"] = json!(sl);
+                region["startColumn"] = json!(sc);
+                region["endLine"] = json!(el);
+                region["endColumn"] = json!(ec);
+            }
+        }
+
+        let place = match m.function_label() {
+            Some(f) => format!(" in `{f}`"),
+            None => String::new(),
+        };
+
+        results.push(json!({
+            "ruleId": rule_id(m),
+            "ruleIndex": rule_index[m.operator.name.as_str()],
+            "level": "warning",
+            "message": {
+                "text": format!(
+                    "Mutant #{} survived: replacing `{}` with `{}`{} did not fail any test.",
+                    m.id, m.original_snippet, m.mutated_snippet, place
+                ),
+            },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": {
+                        "uri": m.span.file.to_string_lossy().replace('\\', "/"),
+                        "uriBaseId": PROJECT_ROOT_BASE_ID,
+                    },
+                    "region": region,
+                },
+            }],
+            "partialFingerprints": { "zkMutantFingerprint/v1": m.fingerprint },
+            "properties": {
+                "mutantId": m.id,
+                "operator": m.operator.name,
+                "originalSnippet": m.original_snippet,
+                "mutatedSnippet": m.mutated_snippet,
+                "durationMs": m.duration_ms,
+            },
+        }));
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": report.tool,
+                    "version": report.version,
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                PROJECT_ROOT_BASE_ID: { "uri": dir_uri(&report.project_root) },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
+
+fn rule_id(m: &Mutant) -> String {
+    format!("zk-mutant/{}", m.operator.name)
+}
+
+/// `file://` URI for a directory (with trailing slash), as SARIF base IDs require.
+fn dir_uri(dir: &Path) -> String {
+    let abs = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let mut path = abs.to_string_lossy().replace('\\', "/");
+    if !path.starts_with('/') {
+        path.insert(0, '/');
+    }
+    if !path.ends_with('/') {
+        path.push('/');
+    }
+    format!("file://{}", path.replace(' ', "%20"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_report;
+
+    #[test]
+    fn render_sarif_reports_survivors_with_regions_and_rules() {
+        let (project, report) = fixture_report(&[MutantOutcome::Killed, MutantOutcome::Survived]);
+
+        let sarif = render_sarif(&project, &report);
+        let run = &sarif["runs"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+
+        let m = &report.mutants[1];
+        let result = &results[0];
+        let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(result["ruleId"], format!("zk-mutant/{}", m.operator.name));
+        assert_eq!(
+            run["tool"]["driver"]["rules"][rule_index]["id"],
+            result["ruleId"]
+        );
+
+        let region = &result["locations"][0]["physicalLocation"]["region"];
+        let (line, col) = project
+            .sources()
+            .line_col(&m.span.file, m.span.start as usize)
+            .unwrap();
+        assert_eq!(region["startLine"], line);
+        assert_eq!(region["startColumn"], col);
+        assert_eq!(region["snippet"]["text"], m.original_snippet);
+        assert!(
+            result["message"]["text"]
+                .as_str()
+                .unwrap()
+                .contains(&format!("with `{}`", m.mutated_snippet))
+        );
+        assert_eq!(
+            result["partialFingerprints"]["zkMutantFingerprint/v1"],
+            m.fingerprint
+        );
+    }
+
+    #[test]
+    fn dir_uri_has_scheme_and_trailing_slash() {
+        let uri = dir_uri(Path::new("tests/fixtures/simple_noir"));
+        assert!(uri.starts_with("file:///"), "{uri}");
+        assert!(uri.ends_with("/simple_noir/"), "{uri}");
+    }
+}
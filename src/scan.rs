@@ -1,10 +1,18 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
 
+use crate::discover::qualified_function_name;
+use crate::mutant::Mutant;
 use crate::project::Project;
-use noir_metrics::MetricsReport;
+use crate::syntax::{
+    TokenKind, enclosing_function, find_functions, find_scopes, is_test_function, outer_attributes,
+    tokenize,
+};
+use noir_metrics::{FileMetrics, MetricsReport};
+use serde::Serialize;
 
 /// High-level overview of a Noir project used by zk-mutant.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProjectOverview {
     /// Absolute path to the project root.
     pub root: PathBuf,
@@ -51,6 +59,404 @@ impl ProjectOverview {
     }
 }
 
+/// Discovered mutants counted by category, operator, file and enclosing function.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MutationInventory {
+    /// Number of discovered mutants.
+    pub total: usize,
+
+    /// Counts keyed by operator category (for example `Condition`).
+    pub by_category: BTreeMap<String, usize>,
+
+    /// Counts keyed by `<category>/<operator>` (for example `Condition/eq_to_neq`).
+    pub by_operator: BTreeMap<String, usize>,
+
+    /// Counts keyed by project-relative file path.
+    pub by_file: BTreeMap<String, usize>,
+
+    /// Counts keyed by qualified function name (mutants outside functions are skipped).
+    pub by_function: BTreeMap<String, usize>,
+}
+
+impl MutationInventory {
+    pub fn from_mutants(mutants: &[Mutant]) -> Self {
+        let mut inventory = MutationInventory {
+            total: mutants.len(),
+            ..Default::default()
+        };
+
+        for m in mutants {
+            let cat = format!("{:?}", m.operator.category);
+            *inventory
+                .by_operator
+                .entry(format!("{cat}/{}", m.operator.name))
+                .or_insert(0) += 1;
+            *inventory.by_category.entry(cat).or_insert(0) += 1;
+
+            let file = m.span.file.to_string_lossy().replace('\\', "/");
+            *inventory.by_file.entry(file).or_insert(0) += 1;
+
+            if let Some(f) = m.function_label() {
+                *inventory.by_function.entry(f.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        inventory
+    }
+}
+
+/// noir-metrics data for one file plus the mutants discovered in it.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileScan {
+    #[serde(flatten)]
+    pub metrics: FileMetrics,
+
+    /// Number of mutants discovered in the file.
+    pub mutants: usize,
+
+    /// Mutants per 100 non-test code lines; `None` for files without non-test code.
+    pub mutation_density: Option<f64>,
+}
+
+/// One [`FileScan`] per project file, in path order.
+pub fn scan_files(project: &Project, inventory: &MutationInventory) -> Vec<FileScan> {
+    let mut files: Vec<FileScan> = project
+        .metrics
+        .files
+        .iter()
+        .map(|fm| {
+            let key = fm.path.to_string_lossy().replace('\\', "/");
+            let mutants = inventory.by_file.get(&key).copied().unwrap_or(0);
+            FileScan {
+                metrics: fm.clone(),
+                mutants,
+                mutation_density: mutation_density(mutants, fm.non_test_lines),
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| a.metrics.path.cmp(&b.metrics.path));
+    files
+}
+
+/// How a function is reached from `#[test]` functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestReference {
+    /// A test names the function in its body.
+    Direct,
+
+    /// Only reached through other non-test functions that tests reference.
+    Transitive,
+
+    /// No test reaches the function.
+    None,
+}
+
+impl TestReference {
+    pub fn label(self) -> &'static str {
+        match self {
+            TestReference::Direct => "direct",
+            TestReference::Transitive => "transitive",
+            TestReference::None => "none",
+        }
+    }
+}
+
+/// A non-test function with its mutants, size and test reachability.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionScan {
+    /// Project-relative file path.
+    pub file: String,
+
+    /// Qualified name, as recorded on the function's mutants.
+    pub name: String,
+
+    /// 1-based line of the `fn` keyword.
+    pub line: usize,
+
+    /// Lines of the function (signature and body) that contain code.
+    pub code_lines: usize,
+
+    /// Number of mutants discovered in the function.
+    pub mutants: usize,
+
+    /// Mutants per 100 code lines of the function.
+    pub mutation_density: Option<f64>,
+
+    /// How tests reach the function.
+    pub test_reference: TestReference,
+
+    /// Qualified names of the tests that reach the function.
+    pub tests: Vec<String>,
+
+    /// Mutants but no test references: likely survivors, worth a look before a `run`.
+    pub hot_spot: bool,
+}
+
+/// A function of the project with the names it references.
+struct FunctionNode {
+    file: String,
+    name: String,
+    short_name: String,
+    line: usize,
+    code_lines: usize,
+    is_test: bool,
+    attributes: Vec<String>,
+    has_assert: bool,
+    mutants: usize,
+    refs: BTreeSet<String>,
+}
+
+/// Every function of a project, linked by the names their bodies reference.
+///
+/// References are resolved by name (any identifier in a body that names a non-test
+/// function, which covers plain, path and method calls), so same-named functions are
+/// all treated as referenced.
+struct CallGraph {
+    nodes: Vec<FunctionNode>,
+    by_name: BTreeMap<String, Vec<usize>>,
+}
+
+impl CallGraph {
+    fn build(project: &Project, mutants: &[Mutant]) -> Self {
+        let mut nodes: Vec<FunctionNode> = Vec::new();
+
+        let mut mutants_by_file: BTreeMap<&Path, Vec<&Mutant>> = BTreeMap::new();
+        for m in mutants {
+            mutants_by_file
+                .entry(m.span.file.as_path())
+                .or_default()
+                .push(m);
+        }
+
+        for (path, text) in project.sources().iter() {
+            let code = text.code();
+            let tokens = tokenize(code);
+            let functions = find_functions(code, &tokens);
+            let scopes = find_scopes(code, &tokens);
+            let file = path.to_string_lossy().replace('\\', "/");
+
+            // One line lookup per token and one function lookup per mutant, shared by
+            // every function in the file.
+            let token_lines: Vec<usize> = tokens
+                .iter()
+                .map(|t| text.line_col(t.start).map_or(0, |(line, _)| line))
+                .collect();
+            let mut own_mutants: BTreeMap<usize, usize> = BTreeMap::new();
+            for m in mutants_by_file.get(path).into_iter().flatten() {
+                if let Some(f) = enclosing_function(&functions, m.span.start as usize) {
+                    *own_mutants.entry(f.start).or_default() += 1;
+                }
+            }
+
+            for f in &functions {
+                let first = tokens.partition_point(|t| t.start < f.start);
+                let last = tokens.partition_point(|t| t.end <= f.body.end);
+                let lines: BTreeSet<usize> = token_lines[first..last.max(first)]
+                    .iter()
+                    .copied()
+                    .collect();
+                let refs: BTreeSet<String> = tokens[first..last.max(first)]
+                    .iter()
+                    .filter(|t| f.body.start <= t.start)
+                    .filter(|t| t.kind == TokenKind::Ident)
+                    .map(|t| t.text(code).to_string())
+                    .collect();
+
+                let attributes = outer_attributes(code, &tokens, f.start);
+
+                nodes.push(FunctionNode {
+                    file: file.clone(),
+                    name: qualified_function_name(path, &scopes, f),
+                    short_name: f.name.clone(),
+                    line: text.line_col(f.start).map(|(line, _)| line).unwrap_or(0),
+                    code_lines: lines.len(),
+                    is_test: is_test_function(&attributes),
+                    attributes,
+                    has_assert: refs.iter().any(|r| ASSERT_MACROS.contains(&r.as_str())),
+                    mutants: own_mutants.get(&f.start).copied().unwrap_or(0),
+                    refs,
+                });
+            }
+        }
+
+        let mut by_name: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, n) in nodes.iter().enumerate().filter(|(_, n)| !n.is_test) {
+            by_name.entry(n.short_name.clone()).or_default().push(i);
+        }
+
+        Self { nodes, by_name }
+    }
+
+    /// Non-test functions referenced from the body of function `i`.
+    fn callees(&self, i: usize) -> Vec<usize> {
+        self.nodes[i]
+            .refs
+            .iter()
+            .filter_map(|r| self.by_name.get(r))
+            .flatten()
+            .copied()
+            .filter(|&j| j != i)
+            .collect()
+    }
+
+    fn tests(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len()).filter(|&i| self.nodes[i].is_test)
+    }
+}
+
+/// Scan every non-test function: mutant count, code lines and whether a `#[test]`
+/// function references it directly or through other functions (see [`CallGraph`]).
+///
+/// Hot spots come first, then functions with more mutants.
+pub fn scan_functions(project: &Project, mutants: &[Mutant]) -> Vec<FunctionScan> {
+    let graph = CallGraph::build(project, mutants);
+    let nodes = &graph.nodes;
+
+    // Walk the reference graph from each test; the first hop is a direct reference.
+    let mut direct = vec![false; nodes.len()];
+    let mut tests: Vec<BTreeSet<String>> = vec![BTreeSet::new(); nodes.len()];
+    for t in graph.tests() {
+        let mut seen = BTreeSet::new();
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for j in graph.callees(t) {
+            direct[j] = true;
+            if seen.insert(j) {
+                queue.push_back(j);
+            }
+        }
+        while let Some(j) = queue.pop_front() {
+            tests[j].insert(nodes[t].name.clone());
+            for k in graph.callees(j) {
+                if seen.insert(k) {
+                    queue.push_back(k);
+                }
+            }
+        }
+    }
+
+    let mut out: Vec<FunctionScan> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| !n.is_test)
+        .map(|(i, n)| {
+            let test_reference = if direct[i] {
+                TestReference::Direct
+            } else if !tests[i].is_empty() {
+                TestReference::Transitive
+            } else {
+                TestReference::None
+            };
+            FunctionScan {
+                file: n.file.clone(),
+                name: n.name.clone(),
+                line: n.line,
+                code_lines: n.code_lines,
+                mutants: n.mutants,
+                mutation_density: mutation_density(n.mutants, n.code_lines),
+                test_reference,
+                tests: tests[i].iter().cloned().collect(),
+                hot_spot: n.mutants > 0 && test_reference == TestReference::None,
+            }
+        })
+        .collect();
+
+    out.sort_by(|a, b| {
+        b.hot_spot
+            .cmp(&a.hot_spot)
+            .then_with(|| b.mutants.cmp(&a.mutants))
+            .then_with(|| (&a.file, a.line).cmp(&(&b.file, b.line)))
+    });
+    out
+}
+
+/// Identifiers that count as assertions in a test body.
+const ASSERT_MACROS: &[&str] = &["assert", "assert_eq"];
+
+/// Why a `#[test]` function is likely to let mutants survive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeakTestReason {
+    /// The body has no `assert` or `assert_eq`.
+    NoAssertions,
+
+    /// The body references no non-test function.
+    NoCodeUnderTest,
+
+    /// `#[test(should_fail)]` passes on any failure, including unrelated ones.
+    ShouldFailWithoutMessage,
+}
+
+impl WeakTestReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            WeakTestReason::NoAssertions => "no assert/assert_eq",
+            WeakTestReason::NoCodeUnderTest => "calls no non-test code",
+            WeakTestReason::ShouldFailWithoutMessage => "should_fail without should_fail_with",
+        }
+    }
+}
+
+/// A `#[test]` function flagged by [`find_weak_tests`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WeakTest {
+    /// Project-relative file path.
+    pub file: String,
+
+    /// Qualified test name.
+    pub name: String,
+
+    /// 1-based line of the `fn` keyword.
+    pub line: usize,
+
+    /// Every reason the test was flagged.
+    pub reasons: Vec<WeakTestReason>,
+}
+
+/// Flag `#[test]` functions without assertions, without references to non-test code,
+/// or marked `should_fail` without `should_fail_with`, in file and line order.
+pub fn find_weak_tests(project: &Project) -> Vec<WeakTest> {
+    let graph = CallGraph::build(project, &[]);
+
+    graph
+        .tests()
+        .filter_map(|t| {
+            let n = &graph.nodes[t];
+            let mut reasons = Vec::new();
+            if !n.has_assert {
+                reasons.push(WeakTestReason::NoAssertions);
+            }
+            if graph.callees(t).is_empty() {
+                reasons.push(WeakTestReason::NoCodeUnderTest);
+            }
+            let should_fail_only = n
+                .attributes
+                .iter()
+                .filter(|a| a.starts_with("test("))
+                .any(|a| a.contains("should_fail") && !a.contains("should_fail_with"));
+            if should_fail_only {
+                reasons.push(WeakTestReason::ShouldFailWithoutMessage);
+            }
+
+            (!reasons.is_empty()).then(|| WeakTest {
+                file: n.file.clone(),
+                name: n.name.clone(),
+                line: n.line,
+                reasons,
+            })
+        })
+        .collect()
+}
+
+/// Mutants per 100 lines, or `None` when there are no lines.
+pub fn mutation_density(mutants: usize, lines: usize) -> Option<f64> {
+    if lines == 0 {
+        None
+    } else {
+        Some(mutants as f64 * 100.0 / lines as f64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +473,173 @@ mod tests {
 
         insta::assert_debug_snapshot!("scan_simple_noir", overview);
     }
+
+    #[test]
+    fn inventory_and_file_scans_count_fixture_mutants() {
+        let root = PathBuf::from("tests/fixtures/simple_noir");
+        let project = Project::from_root(root).expect("Project::from_root should succeed");
+        let mutants = crate::discover::discover_mutants(&project);
+
+        let inventory = MutationInventory::from_mutants(&mutants);
+        assert_eq!(inventory.total, 4);
+        assert_eq!(inventory.by_category["Condition"], 4);
+        assert_eq!(inventory.by_operator["Condition/eq_to_neq"], 2);
+        assert_eq!(inventory.by_function["utils::check_addition"], 1);
+
+        let files = scan_files(&project, &inventory);
+        let summary: Vec<_> = files
+            .iter()
+            .map(|f| (f.metrics.path.clone(), f.mutants, f.mutation_density))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (PathBuf::from("src/main.nr"), 2, Some(2.0 * 100.0 / 6.0)),
+                (PathBuf::from("src/utils.nr"), 2, Some(2.0 * 100.0 / 6.0)),
+            ]
+        );
+        assert_eq!(mutation_density(3, 0), None);
+    }
+
+    /// Single-file project in a temp dir (kept alive by the returned `TempDir`).
+    fn temp_project(main_nr: &str) -> (tempfile::TempDir, Project) {
+        let td = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            td.path().join("Nargo.toml"),
+            "[package]\nname = \"calls\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(td.path().join("src")).unwrap();
+        std::fs::write(td.path().join("src/main.nr"), main_nr).unwrap();
+        let project = Project::from_root(td.path().to_path_buf()).unwrap();
+        (td, project)
+    }
+
+    #[test]
+    fn scan_functions_follows_test_references() {
+        let (_td, project) = temp_project(
+            "fn main(x: u64) {\n    outer(x);\n}\n\n\
+             fn outer(x: u64) {\n    inner(x);\n}\n\n\
+             fn inner(x: u64) {\n    assert(x != 0);\n}\n\n\
+             fn lonely(x: u64) {\n    assert(x == 3);\n    assert(x < 9);\n}\n\n\
+             #[test]\nfn test_outer() {\n    outer(1);\n}\n",
+        );
+        let mutants = crate::discover::discover_mutants(&project);
+        let functions = scan_functions(&project, &mutants);
+
+        let summary: Vec<_> = functions
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.mutants,
+                    f.code_lines,
+                    f.test_reference,
+                    f.tests.join(","),
+                    f.hot_spot,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("lonely", 2, 4, TestReference::None, String::new(), true),
+                (
+                    "inner",
+                    1,
+                    3,
+                    TestReference::Transitive,
+                    "test_outer".into(),
+                    false
+                ),
+                ("main", 0, 3, TestReference::None, String::new(), false),
+                (
+                    "outer",
+                    0,
+                    3,
+                    TestReference::Direct,
+                    "test_outer".into(),
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_weak_tests_flags_each_reason() {
+        let (_td, project) = temp_project(
+            "fn check(x: u64) {\n    assert(x != 0);\n}\n\n\
+             #[test]\nfn strong() {\n    check(1);\n    assert_eq(1, 1);\n}\n\n\
+             #[test]\nfn no_assert() {\n    check(1);\n}\n\n\
+             #[test]\nfn no_code() {\n    assert(1 == 1);\n}\n\n\
+             #[test(should_fail)]\nfn any_failure() {\n    check(0);\n    assert(true);\n}\n\n\
+             #[test(should_fail_with = \"zero\")]\nfn named_failure() {\n    check(0);\n    assert(true);\n}\n",
+        );
+
+        let weak: Vec<_> = find_weak_tests(&project)
+            .into_iter()
+            .map(|t| (t.name, t.line, t.reasons))
+            .collect();
+        assert_eq!(
+            weak,
+            [
+                (
+                    "no_assert".to_string(),
+                    12,
+                    vec![WeakTestReason::NoAssertions]
+                ),
+                (
+                    "no_code".to_string(),
+                    17,
+                    vec![WeakTestReason::NoCodeUnderTest]
+                ),
+                (
+                    "any_failure".to_string(),
+                    22,
+                    vec![WeakTestReason::ShouldFailWithoutMessage]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tests_are_recognised_behind_modifiers() {
+        let (_td, project) = temp_project(
+            "fn check(x: u64) {\n    assert(x != 0);\n}\n\n\
+             #[test]\nunconstrained fn via_unconstrained() {\n    check(1);\n}\n\n\
+             #[test]\npub(crate) fn via_visibility() {\n    assert(1 == 1);\n}\n",
+        );
+        let mutants = crate::discover::discover_mutants(&project);
+
+        let functions: Vec<_> = scan_functions(&project, &mutants)
+            .into_iter()
+            .map(|f| (f.name, f.test_reference, f.tests))
+            .collect();
+        assert_eq!(
+            functions,
+            [(
+                "check".to_string(),
+                TestReference::Direct,
+                vec!["via_unconstrained".to_string()]
+            )]
+        );
+
+        let weak: Vec<_> = find_weak_tests(&project)
+            .into_iter()
+            .map(|t| (t.name, t.reasons))
+            .collect();
+        assert_eq!(
+            weak,
+            [
+                (
+                    "via_unconstrained".to_string(),
+                    vec![WeakTestReason::NoAssertions]
+                ),
+                (
+                    "via_visibility".to_string(),
+                    vec![WeakTestReason::NoCodeUnderTest]
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,158 @@
+use anyhow::Result;
+
+use crate::mutant::Mutant;
+use crate::project::Project;
+use crate::report::format_mutant_with_location;
+
+/// Default number of source lines printed before and after a mutant by `show`.
+pub const DEFAULT_SHOW_CONTEXT_LINES: usize = 3;
+
+/// Find a mutant by numeric id or by fingerprint (a unique prefix is enough).
+pub fn find_mutant<'a>(mutants: &'a [Mutant], target: &str) -> Result<&'a Mutant> {
+    let target = target.trim().trim_start_matches('#');
+
+    if let Ok(id) = target.parse::<u64>() {
+        if let Some(m) = mutants.iter().find(|m| m.id == id) {
+            return Ok(m);
+        }
+    }
+
+    let matches: Vec<&Mutant> = mutants
+        .iter()
+        .filter(|m| !m.fingerprint.is_empty() && m.fingerprint.starts_with(target))
+        .collect();
+    match matches.as_slice() {
+        [m] => Ok(m),
+        [] => anyhow::bail!(
+            "no mutant with id or fingerprint `{target}` ({} mutants discovered; see `zk-mutant list`)",
+            mutants.len()
+        ),
+        _ => anyhow::bail!(
+            "fingerprint prefix `{target}` matches {} mutants (ids {}); use more characters",
+            matches.len(),
+            matches
+                .iter()
+                .map(|m| m.id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Render a mutant with `context` lines of surrounding source, a caret line under the
+/// span and the mutated line.
+pub fn render_mutant_in_context(project: &Project, m: &Mutant, context: usize) -> Result<String> {
+    let text = project
+        .sources()
+        .get(&m.span.file)
+        .ok_or_else(|| anyhow::anyhow!("source file {:?} not part of project", m.span.file))?;
+
+    let start = m.span.start as usize;
+    let end = m.span.end as usize;
+    if !text.matches(start, end, &m.original_snippet) {
+        anyhow::bail!(
+            "mutant {} no longer matches {:?} (source changed since discovery?)",
+            m.id,
+            m.span.file
+        );
+    }
+    let index = text.index();
+    let (line, col) = index.line_col(start).expect("span checked above");
+    let located = index
+        .locate(start, end, &m.mutated_snippet)
+        .expect("span checked above");
+
+    let first = line.saturating_sub(context).max(1);
+    let last = (line + context).min(index.line_count()).max(line);
+    let width = last.to_string().len();
+
+    let mut out = String::new();
+    out.push_str(&format_mutant_with_location(project, m));
+    out.push('\n');
+    if !m.fingerprint.is_empty() {
+        out.push_str(&format!("fingerprint: {}\n", m.fingerprint));
+    }
+    if let Some(f) = m.function_label() {
+        out.push_str(&format!("function:    {f}\n"));
+    }
+    if let Some(ctx) = &m.impl_context {
+        out.push_str(&format!("context:     {ctx}\n"));
+    }
+    out.push('\n');
+
+    let text = index.line_text(line).unwrap_or("");
+    for n in first..=last {
+        let source = index.line_text(n).unwrap_or("");
+        out.push_str(format!("{n:>width$} | {source}").trim_end());
+        out.push('\n');
+        if n == line {
+            out.push_str(&format!(
+                "{:width$} | {}\n",
+                "",
+                caret_line(text, col, &m.original_snippet)
+            ));
+        }
+    }
+
+    out.push_str("\nmutated:\n");
+    out.push_str(&format!("{line:>width$} | {}\n", located.mutated_line));
+
+    Ok(out)
+}
+
+/// Spaces up to `col` (tabs kept so the caret lines up) and one `^` per character of
+/// the snippet's first line.
+fn caret_line(text: &str, col: usize, snippet: &str) -> String {
+    let pad: String = text
+        .chars()
+        .take(col - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = snippet.lines().next().unwrap_or("").chars().count().max(1);
+    format!("{pad}{}", "^".repeat(carets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+    use std::path::PathBuf;
+
+    fn fixture() -> (Project, Vec<Mutant>) {
+        let project = Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load");
+        let mutants = discover_mutants(&project);
+        (project, mutants)
+    }
+
+    #[test]
+    fn find_mutant_by_id_and_fingerprint_prefix() {
+        let (_, mutants) = fixture();
+
+        assert_eq!(find_mutant(&mutants, "2").unwrap().id, 2);
+        assert_eq!(find_mutant(&mutants, "#3").unwrap().id, 3);
+
+        let fp = &mutants[3].fingerprint;
+        assert_eq!(find_mutant(&mutants, &fp[..12]).unwrap().id, 4);
+
+        let err = find_mutant(&mutants, "99").unwrap_err();
+        assert!(err.to_string().contains("no mutant"), "{err}");
+    }
+
+    #[test]
+    fn render_mutant_in_context_snapshot_fixture() {
+        let (project, mutants) = fixture();
+        let mut m = mutants[1].clone();
+        m.fingerprint = "<fingerprint>".to_string();
+
+        insta::assert_snapshot!(
+            "show_mutant",
+            render_mutant_in_context(&project, &m, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn caret_line_keeps_tabs() {
+        assert_eq!(caret_line("\tx == y", 4, "=="), "\t  ^^");
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::line_index::{LineIndex, line_starts};
+use crate::source::SourceFile;
+
+/// Contents of one source file together with its line-start table.
+#[derive(Debug, Clone)]
+pub struct SourceText {
+    code: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceText {
+    pub fn new(code: String) -> Self {
+        let line_starts = line_starts(&code);
+        Self { code, line_starts }
+    }
+
+    /// Full file contents.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Line index over the file, reusing the precomputed line starts.
+    pub fn index(&self) -> LineIndex<'_> {
+        LineIndex::new(&self.code, &self.line_starts)
+    }
+
+    /// 1-based `(line, column)` of a byte offset (see [`LineIndex::line_col`]).
+    pub fn line_col(&self, offset: usize) -> Option<(usize, usize)> {
+        self.index().line_col(offset)
+    }
+
+    /// Whether `start..end` still holds `snippet`.
+    pub fn matches(&self, start: usize, end: usize, snippet: &str) -> bool {
+        self.code.get(start..end) == Some(snippet)
+    }
+}
+
+/// A source file that could not be read when the project was loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnreadableSource {
+    /// Project-relative path.
+    pub file: PathBuf,
+
+    /// Why reading failed.
+    pub error: String,
+}
+
+/// Every source file of a project, read once and keyed by project-relative path.
+///
+/// Reports, discovery and the runner look files up here instead of re-reading them for
+/// each mutant.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: BTreeMap<PathBuf, SourceText>,
+    unreadable: Vec<UnreadableSource>,
+}
+
+impl SourceMap {
+    /// Read every file in `files`.
+    ///
+    /// Files that cannot be read are left out (so they get no mutants) and recorded in
+    /// [`SourceMap::unreadable`] for the caller to report.
+    pub fn load(files: &[SourceFile]) -> Self {
+        let mut map = Self::default();
+        for src in files {
+            match src.read_to_string() {
+                Ok(code) => map.insert(src.relative_path().to_path_buf(), code),
+                Err(e) => map.unreadable.push(UnreadableSource {
+                    file: src.relative_path().to_path_buf(),
+                    error: format!("{e:#}"),
+                }),
+            }
+        }
+        map
+    }
+
+    /// Files that could not be read by [`SourceMap::load`].
+    pub fn unreadable(&self) -> &[UnreadableSource] {
+        &self.unreadable
+    }
+
+    /// Add or replace a file.
+    pub fn insert(&mut self, file: PathBuf, code: String) {
+        self.files.insert(file, SourceText::new(code));
+    }
+
+    /// Look up a file by its project-relative path.
+    pub fn get(&self, file: &Path) -> Option<&SourceText> {
+        self.files.get(file)
+    }
+
+    /// Contents of a file, if it is part of the map.
+    pub fn code(&self, file: &Path) -> Option<&str> {
+        self.get(file).map(SourceText::code)
+    }
+
+    /// 1-based `(line, column)` of a byte offset in `file`.
+    pub fn line_col(&self, file: &Path, offset: usize) -> Option<(usize, usize)> {
+        self.get(file)?.line_col(offset)
+    }
+
+    /// Files in path order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &SourceText)> {
+        self.files.iter().map(|(p, t)| (p.as_path(), t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_col_basic() {
+        let text = SourceText::new("a\nbcd\nef".to_string());
+        let len = text.code().len();
+        assert_eq!(text.line_col(0), Some((1, 1)));
+        assert_eq!(text.line_col(1), Some((1, 2)));
+        assert_eq!(text.line_col(2), Some((2, 1)));
+        assert_eq!(text.line_col(4), Some((2, 3)));
+        assert_eq!(text.line_col(6), Some((3, 1)));
+        assert_eq!(text.line_col(len), Some((3, 3)));
+        assert_eq!(text.line_col(len + 1), None);
+    }
+
+    #[test]
+    fn load_reads_files_once_and_indexes_lines() {
+        let root = Path::new("tests/fixtures/simple_noir");
+        let map = SourceMap::load(&[SourceFile::from_relative(root, Path::new("src/main.nr"))]);
+
+        let main = Path::new("src/main.nr");
+        let code = map.code(main).unwrap();
+        let offset = code.find("x < y").unwrap() + 2;
+        assert_eq!(map.line_col(main, offset), Some((4, 14)));
+
+        let loc = map
+            .get(main)
+            .unwrap()
+            .index()
+            .locate(offset, offset + 1, ">=")
+            .unwrap();
+        assert_eq!(loc.mutated_line, "    assert(x >= y);");
+        assert!(map.get(main).unwrap().matches(offset, offset + 1, "<"));
+        assert!(map.get(Path::new("src/utils.nr")).is_none());
+
+        assert!(map.unreadable().is_empty());
+
+        // Unreadable files are recorded and skipped; the rest still load.
+        let map = SourceMap::load(&[
+            SourceFile::from_relative(root, Path::new("src/missing.nr")),
+            SourceFile::from_relative(root, Path::new("src/utils.nr")),
+        ]);
+        assert!(map.get(Path::new("src/missing.nr")).is_none());
+        assert!(map.get(Path::new("src/utils.nr")).is_some());
+        assert_eq!(map.unreadable().len(), 1);
+        assert_eq!(map.unreadable()[0].file, Path::new("src/missing.nr"));
+    }
+}
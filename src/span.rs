@@ -15,3 +15,27 @@ pub struct SourceSpan {
     /// End byte offset (exclusive).
     pub end: u32,
 }
+
+/// 1-based line and column (columns count characters).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Human-facing position of a span, computed once at discovery so consumers of
+/// `mutants.json` and `run.json` do not have to re-read sources.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SpanLocation {
+    /// Position of the first byte of the span.
+    pub start: LineColumn,
+
+    /// Position just past the last byte of the span.
+    pub end: LineColumn,
+
+    /// Full source line(s) containing the span, without the line terminator.
+    pub source_line: String,
+
+    /// `source_line` with the span replaced by the mutated snippet.
+    pub mutated_line: String,
+}
@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::run_report::MutationRunReport;
+use crate::source_map::SourceText;
+use crate::syntax::{find_functions, tokenize};
+
+/// Version of the mutation-testing-report-schema we emit.
+const SCHEMA_VERSION: &str = "2";
+
+/// Language tag for source files. The report viewers have no Noir grammar; Rust is the
+/// closest supported highlighter.
+const SOURCE_LANGUAGE: &str = "rust";
+
+/// Root of a `mutation-report.json` (mutation-testing-elements schema, version 2).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationTestResult {
+    pub schema_version: &'static str,
+    pub thresholds: Thresholds,
+    pub project_root: String,
+    pub files: BTreeMap<String, FileResult>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub test_files: BTreeMap<String, TestFile>,
+    pub framework: Framework,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Thresholds {
+    pub high: u32,
+    pub low: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileResult {
+    pub language: &'static str,
+    pub source: String,
+    pub mutants: Vec<MutantResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MutantResult {
+    pub id: String,
+    pub mutator_name: String,
+    pub replacement: String,
+    pub location: Location,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub killed_by: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Location {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestFile {
+    pub tests: Vec<TestDefinition>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestDefinition {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Framework {
+    pub name: String,
+    pub version: String,
+}
+
+/// Write `mutation-report.json` for mutation-testing-elements dashboards and viewers.
+pub fn write_mutation_report(
+    out_dir: &Path,
+    project: &Project,
+    report: &MutationRunReport,
+) -> Result<()> {
+    let result = build_mutation_report(project, report);
+    let path = out_dir.join("mutation-report.json");
+    let json = serde_json::to_string_pretty(&result).context("serialize mutation report")?;
+    fs::write(&path, json).with_context(|| format!("failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// Convert a run report into the mutation-testing-report-schema shape.
+///
+/// Files whose source cannot be read are omitted (the schema requires the source).
+pub fn build_mutation_report(project: &Project, report: &MutationRunReport) -> MutationTestResult {
+    let mut by_file: BTreeMap<&Path, Vec<&Mutant>> = BTreeMap::new();
+    for m in &report.mutants {
+        by_file.entry(m.span.file.as_path()).or_default().push(m);
+    }
+
+    let mut files = BTreeMap::new();
+    for (file, mut mutants) in by_file {
+        let Some(text) = project.sources().get(file) else {
+            continue;
+        };
+        mutants.sort_by_key(|m| m.id);
+
+        let results = mutants
+            .into_iter()
+            .filter_map(|m| mutant_result(text, m))
+            .collect();
+
+        files.insert(
+            file.to_string_lossy().replace('\\', "/"),
+            FileResult {
+                language: SOURCE_LANGUAGE,
+                source: text.code().to_string(),
+                mutants: results,
+            },
+        );
+    }
+
+    MutationTestResult {
+        schema_version: SCHEMA_VERSION,
+        thresholds: Thresholds { high: 80, low: 60 },
+        project_root: report.project_root.to_string_lossy().replace('\\', "/"),
+        files,
+        test_files: test_files(project, report),
+        framework: Framework {
+            name: report.tool.clone(),
+            version: report.version.clone(),
+        },
+    }
+}
+
+fn mutant_result(source: &SourceText, m: &Mutant) -> Option<MutantResult> {
+    let (sl, sc) = source.line_col(m.span.start as usize)?;
+    let (el, ec) = source.line_col(m.span.end as usize)?;
+
+    let (status, status_reason) = match m.outcome {
+        MutantOutcome::Killed => ("Killed", None),
+        MutantOutcome::Survived => ("Survived", None),
+        MutantOutcome::Invalid => (
+            "CompileError",
+            Some("mutant could not be built or tested".to_string()),
+        ),
+        MutantOutcome::Timeout => ("Timeout", None),
+        MutantOutcome::NotRun => ("Pending", None),
+    };
+
+    let place = match m.function_label() {
+        Some(f) => format!(" in {f}"),
+        None => String::new(),
+    };
+
+    Some(MutantResult {
+        id: m.id.to_string(),
+        mutator_name: m.operator.name.clone(),
+        replacement: m.mutated_snippet.clone(),
+        location: Location {
+            start: Position {
+                line: sl,
+                column: sc,
+            },
+            end: Position {
+                line: el,
+                column: ec,
+            },
+        },
+        status,
+        status_reason,
+        killed_by: m.killed_by.clone(),
+        duration: m.duration_ms,
+        description: format!(
+            "{:?}: replaced `{}` with `{}`{place}",
+            m.operator.category, m.original_snippet, m.mutated_snippet
+        ),
+    })
+}
+
+/// Tests referenced by `killedBy`, grouped by the file that defines them.
+///
+/// A test is attributed to the first source file with a function of that name (the last
+/// `::` segment); tests that cannot be located are listed under the empty file name.
+fn test_files(project: &Project, report: &MutationRunReport) -> BTreeMap<String, TestFile> {
+    let mut names: Vec<&str> = report
+        .mutants
+        .iter()
+        .flat_map(|m| m.killed_by.iter().map(|s| s.as_str()))
+        .collect();
+    names.sort();
+    names.dedup();
+    if names.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let mut defined_in: BTreeMap<String, String> = BTreeMap::new();
+    for (path, text) in project.sources().iter() {
+        let code = text.code();
+        let file = path.to_string_lossy().replace('\\', "/");
+        for f in find_functions(code, &tokenize(code)) {
+            defined_in.entry(f.name).or_insert_with(|| file.clone());
+        }
+    }
+
+    let mut out: BTreeMap<String, TestFile> = BTreeMap::new();
+    for name in names {
+        let short = name.rsplit("::").next().unwrap_or(name);
+        let file = defined_in.get(short).cloned().unwrap_or_default();
+        out.entry(file)
+            .or_insert_with(|| TestFile { tests: Vec::new() })
+            .tests
+            .push(TestDefinition {
+                id: name.to_string(),
+                name: name.to_string(),
+            });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_report;
+
+    #[test]
+    fn build_mutation_report_maps_statuses_locations_and_tests() {
+        let (project, mut report) = fixture_report(&[
+            MutantOutcome::Killed,
+            MutantOutcome::Survived,
+            MutantOutcome::Invalid,
+            MutantOutcome::Timeout,
+        ]);
+        for m in &mut report.mutants {
+            m.duration_ms = Some(5);
+        }
+        report.mutants[0].killed_by =
+            vec!["test_main".to_string(), "missing::test_gone".to_string()];
+
+        let result = build_mutation_report(&project, &report);
+        let mut json = serde_json::to_value(&result).unwrap();
+
+        // Keep the snapshot focused on the mapping, not the fixture sources.
+        for file in json["files"].as_object_mut().unwrap().values_mut() {
+            file["source"] = serde_json::Value::String("<source>".to_string());
+        }
+        json["framework"]["version"] = serde_json::Value::String("<version>".to_string());
+
+        insta::assert_json_snapshot!("mutation_report", json);
+    }
+}
@@ -0,0 +1,475 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::mutant::{Mutant, MutantOutcome};
+use crate::project::Project;
+use crate::report::format_mutant_with_location;
+use crate::syntax::{
+    Token, TokenKind, enclosing_function, find_functions, token_index_at, tokenize,
+};
+
+/// A function parameter as written in the signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Param {
+    name: String,
+    ty: String,
+}
+
+/// Write one suggested test file per surviving mutant to `<out_dir>/suggested_tests/`.
+///
+/// Files are named `<id>_<function>.nr`. Survivors outside a function (or whose source
+/// cannot be read) get no stub. Returns the number of files written.
+pub fn write_suggested_tests(
+    out_dir: &Path,
+    project: &Project,
+    mutants: &[Mutant],
+) -> Result<usize> {
+    let mut survivors: Vec<&Mutant> = mutants
+        .iter()
+        .filter(|m| m.outcome == MutantOutcome::Survived)
+        .collect();
+    if survivors.is_empty() {
+        return Ok(0);
+    }
+    survivors.sort_by_key(|m| m.id);
+
+    let dir = out_dir.join("suggested_tests");
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {:?}", dir))?;
+
+    let mut written = 0;
+    for m in survivors {
+        let Some(stub) = render_test_stub(project, m) else {
+            continue;
+        };
+        let function = m.function.as_deref().unwrap_or("mutant");
+        let path = dir.join(format!("{:06}_{function}.nr", m.id));
+        fs::write(&path, stub).with_context(|| format!("failed to write {:?}", path))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Render a Noir test stub that calls the mutant's enclosing function.
+///
+/// Parameters become `let` bindings with placeholder values typed from the signature.
+/// A header comment describes where the original and mutated expressions differ. When
+/// the mutant sits directly in an `assert(...)`, a `#[test(should_fail)]` stub for
+/// inputs that violate the original condition is added.
+pub fn render_test_stub(project: &Project, m: &Mutant) -> Option<String> {
+    let text = project.sources().get(&m.span.file)?;
+    let start = m.span.start as usize;
+    let end = m.span.end as usize;
+    if !text.matches(start, end, &m.original_snippet) {
+        return None;
+    }
+    let code = text.code();
+
+    let tokens = tokenize(code);
+    let functions = find_functions(code, &tokens);
+    let f = enclosing_function(&functions, start)?;
+    let header = &code[f.start..f.body.start];
+    let params = parse_params(header);
+    let returns = return_type(header);
+
+    let operands = operands(code, &tokens, start, end);
+    let (lhs, rhs) = match &operands {
+        Some(o) => (o.lhs.as_str(), o.rhs.as_str()),
+        None => ("<lhs>", "<rhs>"),
+    };
+    let in_assert = operands.as_ref().is_some_and(|o| o.in_assert);
+    let conditions = conditions(&m.operator.name, lhs, rhs);
+    let file = m.span.file.to_string_lossy().replace('\\', "/");
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "// Suggested test for surviving mutant {}\n",
+        format_mutant_with_location(project, m)
+    ));
+    out.push_str("//\n");
+    out.push_str(&format!(
+        "// `{lhs} {} {rhs}` was replaced with `{lhs} {} {rhs}` in `{}` and every test still passed.\n",
+        m.original_snippet, m.mutated_snippet, f.name
+    ));
+    match &conditions {
+        Some(c) => out.push_str(&format!(
+            "// The original holds when `{}` and fails when `{}`; the mutant does the opposite,\n\
+             // so any input that reaches this expression tells them apart. Boundary: {}.\n",
+            c.holds, c.violated, c.boundary
+        )),
+        None => out.push_str(
+            "// Pick inputs for which the original and mutated expressions evaluate differently.\n",
+        ),
+    }
+    out.push_str(&format!(
+        "// Paste into {file} (or a module that can call `{}`) and fill in the inputs.\n",
+        f.name
+    ));
+
+    let call = format!(
+        "{}({})",
+        f.name,
+        params
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let holds = conditions.as_ref().map(|c| c.holds.as_str());
+    out.push('\n');
+    out.push_str(&test_fn(
+        "#[test]",
+        &format!("test_{}_mutant_{}", f.name, m.id),
+        holds,
+        &params,
+        &call,
+        returns.as_deref(),
+    ));
+
+    if in_assert {
+        let violated = conditions.as_ref().map(|c| c.violated.as_str());
+        out.push('\n');
+        out.push_str(&test_fn(
+            "#[test(should_fail)]",
+            &format!("test_{}_mutant_{}_should_fail", f.name, m.id),
+            violated,
+            &params,
+            &call,
+            None,
+        ));
+    }
+
+    Some(out)
+}
+
+fn test_fn(
+    attribute: &str,
+    name: &str,
+    inputs_where: Option<&str>,
+    params: &[Param],
+    call: &str,
+    returns: Option<&str>,
+) -> String {
+    let mut out = format!("{attribute}\nfn {name}() {{\n");
+    match inputs_where {
+        Some(cond) => out.push_str(&format!("    // TODO: inputs where `{cond}`.\n")),
+        None => out.push_str("    // TODO: choose inputs.\n"),
+    }
+    for p in params {
+        out.push_str(&format!(
+            "    let {}: {} = {};\n",
+            p.name,
+            p.ty,
+            placeholder(&p.ty)
+        ));
+    }
+    match returns {
+        Some(ty) => {
+            out.push_str(&format!("    let result: {ty} = {call};\n"));
+            out.push_str("    // TODO: assert on `result`.\n");
+        }
+        None => out.push_str(&format!("    {call};\n")),
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Where the original and mutated comparison disagree, in terms of the operands.
+struct Conditions {
+    holds: String,
+    violated: String,
+    boundary: String,
+}
+
+fn conditions(operator: &str, l: &str, r: &str) -> Option<Conditions> {
+    let (holds, violated, boundary) = match operator {
+        "eq_to_neq" => ("==", "!=", format!("`{l} == {r}`")),
+        "neq_to_eq" => ("!=", "==", format!("`{l} == {r}`")),
+        "lt_to_ge" => (
+            "<",
+            ">=",
+            format!("`{l} == {r} - 1` (holds) vs `{l} == {r}` (fails)"),
+        ),
+        "le_to_gt" => (
+            "<=",
+            ">",
+            format!("`{l} == {r}` (holds) vs `{l} == {r} + 1` (fails)"),
+        ),
+        "ge_to_lt" => (
+            ">=",
+            "<",
+            format!("`{l} == {r}` (holds) vs `{l} == {r} - 1` (fails)"),
+        ),
+        "gt_to_le" => (
+            ">",
+            "<=",
+            format!("`{l} == {r} + 1` (holds) vs `{l} == {r}` (fails)"),
+        ),
+        _ => return None,
+    };
+    Some(Conditions {
+        holds: format!("{l} {holds} {r}"),
+        violated: format!("{l} {violated} {r}"),
+        boundary,
+    })
+}
+
+/// Operand texts around a binary operator and whether the expression is the
+/// condition of an `assert(...)`.
+struct Operands {
+    lhs: String,
+    rhs: String,
+    in_assert: bool,
+}
+
+/// Find the operands of the operator at `start..end` by walking tokens outwards until
+/// a separator at bracket depth 0 (`,`, `;`, braces, `&&`, `||`, `=`, keywords, or an
+/// unmatched bracket).
+fn operands(code: &str, tokens: &[Token], start: usize, end: usize) -> Option<Operands> {
+    let op = token_index_at(tokens, start);
+    if tokens
+        .get(op)
+        .is_none_or(|t| t.start != start || t.end != end)
+    {
+        return None;
+    }
+
+    let mut first = op;
+    let mut depth = 0i32;
+    while first > 0 {
+        let t = &tokens[first - 1];
+        let text = t.text(code);
+        match text {
+            ")" | "]" => depth += 1,
+            "(" | "[" if depth == 0 => break,
+            "(" | "[" => depth -= 1,
+            _ if depth == 0 && is_separator(t, text) => break,
+            _ => {}
+        }
+        first -= 1;
+    }
+
+    let mut last = op + 1;
+    let mut depth = 0i32;
+    while last < tokens.len() {
+        let t = &tokens[last];
+        let text = t.text(code);
+        match text {
+            "(" | "[" => depth += 1,
+            ")" | "]" if depth == 0 => break,
+            ")" | "]" => depth -= 1,
+            _ if depth == 0 && is_separator(t, text) => break,
+            _ => {}
+        }
+        last += 1;
+    }
+
+    if first == op || last == op + 1 {
+        return None;
+    }
+
+    let in_assert = first >= 2
+        && tokens[first - 1].text(code) == "("
+        && tokens[first - 2].text(code) == "assert";
+
+    Some(Operands {
+        lhs: code[tokens[first].start..tokens[op - 1].end].to_string(),
+        rhs: code[tokens[op + 1].start..tokens[last - 1].end].to_string(),
+        in_assert,
+    })
+}
+
+fn is_separator(t: &Token, text: &str) -> bool {
+    match t.kind {
+        TokenKind::Punct => matches!(
+            text,
+            "," | ";"
+                | "{"
+                | "}"
+                | "&&"
+                | "||"
+                | "="
+                | "=>"
+                | "=="
+                | "!="
+                | "<"
+                | ">"
+                | "<="
+                | ">="
+        ),
+        TokenKind::Ident => matches!(text, "let" | "if" | "else" | "return" | "in" | "for"),
+        _ => false,
+    }
+}
+
+/// Parameters of a function header such as `fn f(x: Field, y: pub u64)`.
+fn parse_params(header: &str) -> Vec<Param> {
+    let Some(open) = header.find('(') else {
+        return Vec::new();
+    };
+    let Some(close) = matching_paren(header, open) else {
+        return Vec::new();
+    };
+
+    split_top_level(&header[open + 1..close])
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, part)| {
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+            let (name, ty) = part.split_once(':').unwrap_or((part, "Field"));
+            let name = name.trim().trim_start_matches("mut ").trim();
+            let name = if name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                name.to_string()
+            } else {
+                format!("arg{i}")
+            };
+            let ty = ty.trim();
+            let ty = ty.strip_prefix("pub ").unwrap_or(ty).trim().to_string();
+            Some(Param { name, ty })
+        })
+        .collect()
+}
+
+/// Return type after `->`, if any (visibility such as `pub` dropped).
+fn return_type(header: &str) -> Option<String> {
+    let open = header.find('(')?;
+    let close = matching_paren(header, open)?;
+    let ty = header[close + 1..].trim().strip_prefix("->")?.trim();
+    let ty = ty.strip_prefix("pub ").unwrap_or(ty).trim();
+    let ty = ty.split(" where ").next().unwrap_or(ty).trim();
+    (!ty.is_empty()).then(|| ty.to_string())
+}
+
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas outside `()`, `[]` and `<>`.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Placeholder value for a Noir type; unknown types get a `TODO` marker so the stub
+/// does not compile until it is filled in.
+fn placeholder(ty: &str) -> String {
+    let ty = ty.trim();
+    match ty {
+        "Field" => return "0".to_string(),
+        "bool" => return "false".to_string(),
+        _ => {}
+    }
+    if let Some(bits) = ty.strip_prefix('u').or_else(|| ty.strip_prefix('i')) {
+        if !bits.is_empty() && bits.chars().all(|c| c.is_ascii_digit()) {
+            return "0".to_string();
+        }
+    }
+    if ty.starts_with("str<") {
+        return "\"\"".to_string();
+    }
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        if let Some((elem, len)) = inner.rsplit_once(';') {
+            return format!("[{}; {}]", placeholder(elem), len.trim());
+        }
+    }
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let items: Vec<String> = split_top_level(inner)
+            .into_iter()
+            .filter(|t| !t.trim().is_empty())
+            .map(placeholder)
+            .collect();
+        return format!("({})", items.join(", "));
+    }
+    format!("/* TODO: {ty} */")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::discover_mutants;
+    use std::path::PathBuf;
+
+    #[test]
+    fn render_test_stub_snapshot_fixture() {
+        let project = Project::from_root(PathBuf::from("tests/fixtures/simple_noir"))
+            .expect("fixture project should load");
+        let mutants = discover_mutants(&project);
+
+        let stubs: Vec<String> = mutants
+            .iter()
+            .filter_map(|m| render_test_stub(&project, m))
+            .collect();
+        insta::assert_snapshot!("suggested_tests", stubs.join("\n// ----\n\n"));
+    }
+
+    #[test]
+    fn parse_params_handles_visibility_and_nested_types() {
+        let params =
+            parse_params("pub fn f(mut x: Field, y: pub [u8; 4], t: (bool, u64)) -> pub u32 ");
+        assert_eq!(
+            params,
+            vec![
+                Param {
+                    name: "x".to_string(),
+                    ty: "Field".to_string()
+                },
+                Param {
+                    name: "y".to_string(),
+                    ty: "[u8; 4]".to_string()
+                },
+                Param {
+                    name: "t".to_string(),
+                    ty: "(bool, u64)".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            return_type("pub fn f(x: Field) -> pub u32 ").as_deref(),
+            Some("u32")
+        );
+        assert_eq!(return_type("fn f(x: Field) "), None);
+    }
+
+    #[test]
+    fn placeholder_values_by_type() {
+        assert_eq!(placeholder("Field"), "0");
+        assert_eq!(placeholder("u64"), "0");
+        assert_eq!(placeholder("bool"), "false");
+        assert_eq!(placeholder("[u8; 4]"), "[0; 4]");
+        assert_eq!(placeholder("(bool, i8)"), "(false, 0)");
+        assert_eq!(placeholder("str<5>"), "\"\"");
+        assert_eq!(placeholder("MyStruct"), "/* TODO: MyStruct */");
+    }
+}
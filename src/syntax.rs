@@ -0,0 +1,601 @@
+use std::ops::Range;
+
+/// Kind of a lexical token in Noir source code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Identifier or keyword (for example `fn`, `assert`, `x`).
+    Ident,
+
+    /// Integer literal (decimal or hex).
+    Number,
+
+    /// String literal, including its quotes.
+    Str,
+
+    /// Operator or punctuation (for example `==`, `{`, `::`).
+    Punct,
+}
+
+/// A token as a byte range into the source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+
+    /// Start byte offset (inclusive).
+    pub start: usize,
+
+    /// End byte offset (exclusive).
+    pub end: usize,
+}
+
+impl Token {
+    /// Source text of this token.
+    pub fn text<'a>(&self, code: &'a str) -> &'a str {
+        &code[self.start..self.end]
+    }
+
+    fn is_punct(&self, code: &str, p: &str) -> bool {
+        self.kind == TokenKind::Punct && self.text(code) == p
+    }
+}
+
+/// A `fn` item with a body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionItem {
+    /// Function name (for example `check_addition`).
+    pub name: String,
+
+    /// Byte offset of the `fn` keyword.
+    pub start: usize,
+
+    /// Byte range of the body, from `{` to just past the matching `}`.
+    pub body: Range<usize>,
+}
+
+/// Kind of a block that qualifies the functions inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// Inline `mod name { ... }`.
+    Mod,
+
+    /// `impl Type { ... }` or `impl Trait for Type { ... }`.
+    Impl,
+
+    /// `trait Name { ... }` (default method bodies live here).
+    Trait,
+}
+
+/// An inline module, `impl` or `trait` block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeItem {
+    pub kind: ScopeKind,
+
+    /// Path segment this scope adds to qualified names: the module name, the `impl`'s
+    /// self type without generics, or the trait name.
+    pub name: String,
+
+    /// Whitespace-normalized header up to the body, for example `impl<T> Eq for Foo<T>`
+    /// (`where` clauses dropped).
+    pub header: String,
+
+    /// Byte range of the body, from `{` to just past the matching `}`.
+    pub body: Range<usize>,
+}
+
+/// Multi-character operators, longest first within each prefix.
+const MULTI_CHAR_PUNCT: &[&str] = &[
+    "<<=", ">>=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>", "+=", "-=",
+    "*=", "/=", "%=", "&=", "|=", "^=", "..",
+];
+
+/// Split Noir source code into tokens.
+///
+/// This is a lightweight lexer, not a full Noir front end:
+/// - whitespace, `// ...` and `/* ... */` comments are skipped
+/// - string literals handle simple escapes
+/// - anything else that is not an identifier or number becomes punctuation
+pub fn tokenize(code: &str) -> Vec<Token> {
+    let bytes = code.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if b.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Comments.
+        if b == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if b == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            i += 2;
+            while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(bytes.len());
+            continue;
+        }
+
+        let start = i;
+
+        let kind = if b == b'"' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(bytes.len());
+            TokenKind::Str
+        } else if b.is_ascii_alphabetic() || b == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            TokenKind::Ident
+        } else if b.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            TokenKind::Number
+        } else {
+            let rest = &code[i..];
+            let len = MULTI_CHAR_PUNCT
+                .iter()
+                .find(|p| rest.starts_with(**p))
+                .map(|p| p.len())
+                .unwrap_or_else(|| rest.chars().next().map(char::len_utf8).unwrap_or(1));
+            i += len;
+            TokenKind::Punct
+        };
+
+        tokens.push(Token {
+            kind,
+            start,
+            end: i.min(bytes.len()),
+        });
+    }
+
+    tokens
+}
+
+/// Find all `fn` items with a body (trait method declarations ending in `;` are skipped).
+pub fn find_functions(code: &str, tokens: &[Token]) -> Vec<FunctionItem> {
+    let mut items = Vec::new();
+
+    let mut idx = 0usize;
+    while idx + 1 < tokens.len() {
+        let tok = &tokens[idx];
+        let name_tok = &tokens[idx + 1];
+
+        if tok.kind != TokenKind::Ident
+            || tok.text(code) != "fn"
+            || name_tok.kind != TokenKind::Ident
+        {
+            idx += 1;
+            continue;
+        }
+
+        // Find the opening `{` of the body, skipping over parameter lists, generics and
+        // array types such as `[u8; 32]` (whose `;` must not end the search).
+        let mut depth: i32 = 0;
+        let mut open = None;
+        let mut j = idx + 2;
+        while j < tokens.len() {
+            let t = &tokens[j];
+            if t.is_punct(code, "(") || t.is_punct(code, "[") {
+                depth += 1;
+            } else if t.is_punct(code, ")") || t.is_punct(code, "]") {
+                depth -= 1;
+            } else if depth == 0 && t.is_punct(code, ";") {
+                break;
+            } else if depth == 0 && t.is_punct(code, "{") {
+                open = Some(j);
+                break;
+            }
+            j += 1;
+        }
+
+        let Some(open) = open else {
+            idx = j.max(idx + 1);
+            continue;
+        };
+
+        let close = matching_brace(code, tokens, open);
+        let body_end = close.map(|c| tokens[c].end).unwrap_or(code.len());
+
+        items.push(FunctionItem {
+            name: name_tok.text(code).to_string(),
+            start: tok.start,
+            body: tokens[open].start..body_end,
+        });
+
+        idx = close.map(|c| c + 1).unwrap_or(tokens.len());
+    }
+
+    items
+}
+
+/// Find inline `mod`, `impl` and `trait` blocks (nested ones included).
+pub fn find_scopes(code: &str, tokens: &[Token]) -> Vec<ScopeItem> {
+    let mut items = Vec::new();
+
+    for (idx, tok) in tokens.iter().enumerate() {
+        if tok.kind != TokenKind::Ident {
+            continue;
+        }
+        let kind = match tok.text(code) {
+            "mod" => ScopeKind::Mod,
+            "impl" => ScopeKind::Impl,
+            "trait" => ScopeKind::Trait,
+            _ => continue,
+        };
+        // `impl` also appears in types (`-> impl Eq`, `x: impl Eq`); only an item opens a scope.
+        if kind == ScopeKind::Impl && !starts_item(code, tokens, idx) {
+            continue;
+        }
+
+        // Header tokens up to the body's `{`; a `;` first means there is no body
+        // (`mod utils;`).
+        let mut depth: i32 = 0;
+        let mut open = None;
+        let mut j = idx + 1;
+        while j < tokens.len() {
+            let t = &tokens[j];
+            if t.is_punct(code, "(") || t.is_punct(code, "[") {
+                depth += 1;
+            } else if t.is_punct(code, ")") || t.is_punct(code, "]") {
+                depth -= 1;
+            } else if depth == 0 && (t.is_punct(code, ";") || t.is_punct(code, "}")) {
+                break;
+            } else if depth == 0 && t.is_punct(code, "{") {
+                open = Some(j);
+                break;
+            }
+            j += 1;
+        }
+        let Some(open) = open else {
+            continue;
+        };
+
+        let header_tokens = &tokens[idx..open];
+        let header_end = header_tokens
+            .iter()
+            .position(|t| t.kind == TokenKind::Ident && t.text(code) == "where")
+            .unwrap_or(header_tokens.len());
+        let header_tokens = &header_tokens[..header_end];
+
+        let Some(name) = scope_name(code, kind, header_tokens) else {
+            continue;
+        };
+
+        let close = matching_brace(code, tokens, open);
+        let body_end = close.map(|c| tokens[c].end).unwrap_or(code.len());
+
+        items.push(ScopeItem {
+            kind,
+            name,
+            header: join_tokens(code, header_tokens),
+            body: tokens[open].start..body_end,
+        });
+    }
+
+    items
+}
+
+/// Whether `tokens[idx]` begins an item: it comes first in the file, or after `{`, `}`,
+/// `;`, an attribute's `]` or `pub`.
+fn starts_item(code: &str, tokens: &[Token], idx: usize) -> bool {
+    let Some(prev) = idx.checked_sub(1).map(|i| &tokens[i]) else {
+        return true;
+    };
+    ["{", "}", ";", "]"].iter().any(|p| prev.is_punct(code, p))
+        || (prev.kind == TokenKind::Ident && prev.text(code) == "pub")
+}
+
+/// Scopes whose body contains `offset`, outermost first.
+pub fn enclosing_scopes(scopes: &[ScopeItem], offset: usize) -> Vec<&ScopeItem> {
+    let mut out: Vec<&ScopeItem> = scopes
+        .iter()
+        .filter(|s| s.body.start <= offset && offset < s.body.end)
+        .collect();
+    out.sort_by_key(|s| std::cmp::Reverse(s.body.end - s.body.start));
+    out
+}
+
+/// Path segment for a scope header (`header[0]` is the `mod`/`impl`/`trait` keyword).
+fn scope_name(code: &str, kind: ScopeKind, header: &[Token]) -> Option<String> {
+    let rest = &header[1..];
+    match kind {
+        ScopeKind::Mod | ScopeKind::Trait => rest
+            .first()
+            .filter(|t| t.kind == TokenKind::Ident)
+            .map(|t| t.text(code).to_string()),
+        ScopeKind::Impl => {
+            let mut rest = skip_generics(code, rest);
+            // `impl Trait for Type`: the self type follows `for` at generic depth 0.
+            let mut depth: i32 = 0;
+            for (k, t) in rest.iter().enumerate() {
+                match t.text(code) {
+                    "<" => depth += 1,
+                    ">" => depth -= 1,
+                    ">>" => depth -= 2,
+                    "for" if depth == 0 && t.kind == TokenKind::Ident => {
+                        rest = &rest[k + 1..];
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let ty = join_tokens(code, rest);
+            let name = ty.split('<').next().unwrap_or("").trim();
+            (!name.is_empty()).then(|| name.to_string())
+        }
+    }
+}
+
+/// Drop a leading `<...>` generic parameter list.
+fn skip_generics<'a>(code: &str, tokens: &'a [Token]) -> &'a [Token] {
+    if tokens.first().is_none_or(|t| t.text(code) != "<") {
+        return tokens;
+    }
+    let mut depth: i32 = 0;
+    for (k, t) in tokens.iter().enumerate() {
+        match t.text(code) {
+            "<" => depth += 1,
+            ">" => depth -= 1,
+            ">>" => depth -= 2,
+            _ => {}
+        }
+        if depth <= 0 {
+            return &tokens[k + 1..];
+        }
+    }
+    &[]
+}
+
+/// Token texts joined with single spaces, without spaces around `<`, `>`, `::` and
+/// before `,` (for example `impl<T> Eq for Foo<T>`).
+fn join_tokens(code: &str, tokens: &[Token]) -> String {
+    let mut out = String::new();
+    let mut prev = "";
+    for t in tokens {
+        let text = t.text(code);
+        let tight = matches!(text, "<" | ">" | ">>" | "::" | "," | ")" | "]" | ";")
+            || matches!(prev, "<" | "::" | "(" | "[" | "&");
+        if !out.is_empty() && !tight {
+            out.push(' ');
+        }
+        out.push_str(text);
+        prev = text;
+    }
+    out
+}
+
+/// Whether a function with these outer attributes (see [`outer_attributes`]) is a test:
+/// `#[test]` or `#[test(...)]`.
+pub fn is_test_function(attributes: &[String]) -> bool {
+    attributes
+        .iter()
+        .any(|a| a == "test" || a.starts_with("test("))
+}
+
+/// Outer attributes of the item whose keyword token is at `item_start`, in source order
+/// and without `#[`/`]` (for example `test(should_fail_with = "overflow")`).
+///
+/// Visibility and `unconstrained`/`comptime` modifiers between the attributes and the
+/// keyword are skipped.
+pub fn outer_attributes(code: &str, tokens: &[Token], item_start: usize) -> Vec<String> {
+    let mut attrs = Vec::new();
+    let mut k = token_index_at(tokens, item_start);
+
+    while k > 0 {
+        let prev = &tokens[k - 1];
+        if prev.kind == TokenKind::Ident
+            && matches!(prev.text(code), "pub" | "unconstrained" | "comptime")
+        {
+            k -= 1;
+        } else if prev.is_punct(code, ")")
+            && k >= 4
+            && tokens[k - 4].text(code) == "pub"
+            && tokens[k - 3].is_punct(code, "(")
+        {
+            // `pub(crate)`
+            k -= 4;
+        } else if prev.is_punct(code, "]") {
+            let mut depth = 0i32;
+            let mut open = None;
+            for j in (0..k).rev() {
+                if tokens[j].is_punct(code, "]") {
+                    depth += 1;
+                } else if tokens[j].is_punct(code, "[") {
+                    depth -= 1;
+                    if depth == 0 {
+                        open = Some(j);
+                        break;
+                    }
+                }
+            }
+            let Some(open) = open.filter(|&o| o > 0 && tokens[o - 1].is_punct(code, "#")) else {
+                break;
+            };
+            // Keep `test(...)` tight: `join_tokens` spaces before `(`.
+            let inner = &tokens[open + 1..k - 1];
+            attrs.push(match inner.split_first() {
+                Some((name, rest)) if rest.first().is_some_and(|t| t.is_punct(code, "(")) => {
+                    format!("{}{}", name.text(code), join_tokens(code, rest))
+                }
+                _ => join_tokens(code, inner),
+            });
+            k = open - 1;
+        } else {
+            break;
+        }
+    }
+
+    attrs.reverse();
+    attrs
+}
+
+/// Return the innermost function whose body contains `offset`.
+pub fn enclosing_function(functions: &[FunctionItem], offset: usize) -> Option<&FunctionItem> {
+    functions
+        .iter()
+        .filter(|f| f.body.start <= offset && offset < f.body.end)
+        .min_by_key(|f| f.body.end - f.body.start)
+}
+
+/// Index of the token that contains `offset` (or the first token starting after it).
+pub fn token_index_at(tokens: &[Token], offset: usize) -> usize {
+    tokens.partition_point(|t| t.end <= offset)
+}
+
+/// Index of the `}` matching the `{` at `open`, if any.
+fn matching_brace(code: &str, tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    for (k, t) in tokens.iter().enumerate().skip(open) {
+        if t.is_punct(code, "{") {
+            depth += 1;
+        } else if t.is_punct(code, "}") {
+            depth -= 1;
+            if depth == 0 {
+                return Some(k);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(code: &'a str, tokens: &[Token]) -> Vec<&'a str> {
+        tokens.iter().map(|t| t.text(code)).collect()
+    }
+
+    #[test]
+    fn outer_attributes_reads_attributes_before_fn() {
+        let code = "#[test(should_fail_with = \"boom\")]\n#[inline]\npub(crate) unconstrained fn t() {}\n\
+                    fn plain() {}\n";
+        let tokens = tokenize(code);
+        let functions = find_functions(code, &tokens);
+
+        assert_eq!(
+            outer_attributes(code, &tokens, functions[0].start),
+            ["test(should_fail_with = \"boom\")", "inline"]
+        );
+        assert!(outer_attributes(code, &tokens, functions[1].start).is_empty());
+    }
+
+    #[test]
+    fn tokenize_skips_comments_and_groups_operators() {
+        let code = "assert(x <= 0x1f); // a == b\n/* c != d */ let s = \"a//b\";";
+        let tokens = tokenize(code);
+
+        assert_eq!(
+            texts(code, &tokens),
+            vec![
+                "assert", "(", "x", "<=", "0x1f", ")", ";", "let", "s", "=", "\"a//b\"", ";"
+            ]
+        );
+        assert_eq!(tokens[3].kind, TokenKind::Punct);
+        assert_eq!(tokens[4].kind, TokenKind::Number);
+        assert_eq!(tokens[10].kind, TokenKind::Str);
+    }
+
+    #[test]
+    fn find_functions_handles_array_types_and_trait_declarations() {
+        let code = r#"
+trait T {
+    fn decl(self) -> Field;
+}
+
+fn hash(input: [u8; 32]) -> [u8; 32] {
+    if input[0] == 1 { input } else { input }
+}
+
+pub fn other() {}
+"#;
+        let tokens = tokenize(code);
+        let fns = find_functions(code, &tokens);
+
+        let names: Vec<&str> = fns.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["hash", "other"]);
+
+        let eq = code.find("==").unwrap();
+        assert_eq!(
+            enclosing_function(&fns, eq).map(|f| f.name.as_str()),
+            Some("hash")
+        );
+        assert_eq!(enclosing_function(&fns, 0), None);
+    }
+
+    #[test]
+    fn find_scopes_names_impls_traits_and_inline_modules() {
+        let code = r#"
+mod utils;
+
+mod math {
+    struct Point<T> { x: T }
+
+    impl<T> Point<T> where T: Eq {
+        fn eq(self, other: Self) -> bool { self.x == other.x }
+    }
+
+    impl Default for Point<Field> {
+        fn default() -> Self { Point { x: 0 } }
+    }
+
+    trait Check {
+        fn check(self) -> bool { 1 == 1 }
+    }
+}
+
+fn make(x: u64) -> impl Eq { assert(x != 1); x }
+
+fn take(x: impl Eq, y: [impl Eq; 2]) {}
+"#;
+        let tokens = tokenize(code);
+        let scopes = find_scopes(code, &tokens);
+
+        let summary: Vec<(ScopeKind, &str, &str)> = scopes
+            .iter()
+            .map(|s| (s.kind, s.name.as_str(), s.header.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ScopeKind::Mod, "math", "mod math"),
+                (ScopeKind::Impl, "Point", "impl<T> Point<T>"),
+                (ScopeKind::Impl, "Point", "impl Default for Point<Field>"),
+                (ScopeKind::Trait, "Check", "trait Check"),
+            ]
+        );
+
+        let eq = code.find("==").unwrap();
+        let names: Vec<&str> = enclosing_scopes(&scopes, eq)
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["math", "Point"]);
+        assert!(enclosing_scopes(&scopes, 0).is_empty());
+
+        // `impl` in return and parameter types does not open a scope.
+        assert!(enclosing_scopes(&scopes, code.find("!=").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn token_index_at_finds_containing_token() {
+        let code = "a == b";
+        let tokens = tokenize(code);
+
+        assert_eq!(token_index_at(&tokens, 2), 1);
+        assert_eq!(token_index_at(&tokens, 3), 1);
+        assert_eq!(token_index_at(&tokens, 5), 2);
+    }
+}
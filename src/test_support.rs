@@ -0,0 +1,84 @@
+//! Shared fixtures for unit tests.
+//!
+//! Tests build mutants through [`mutant`] and override the fields they care about with
+//! struct update syntax, so adding a field to `Mutant` only touches this module.
+
+use std::path::PathBuf;
+
+use crate::discover::discover_mutants;
+use crate::mutant::{Mutant, MutantOutcome, MutationOperator, OperatorCategory};
+use crate::project::Project;
+use crate::run_report::{BaselineReport, MutationRunReport, RunSummary};
+use crate::span::SourceSpan;
+
+/// A not-yet-run condition mutant replacing `original` at the start of `file`.
+pub fn mutant(id: u64, file: &str, operator: &str, original: &str, mutated: &str) -> Mutant {
+    Mutant {
+        id,
+        fingerprint: String::new(),
+        operator: MutationOperator {
+            category: OperatorCategory::Condition,
+            name: operator.to_string(),
+        },
+        span: span(file, 0, original.len() as u32),
+        function: None,
+        module_path: None,
+        impl_context: None,
+        qualified_name: None,
+        location: None,
+        original_snippet: original.to_string(),
+        mutated_snippet: mutated.to_string(),
+        outcome: MutantOutcome::NotRun,
+        duration_ms: None,
+        killed_by: Vec::new(),
+    }
+}
+
+/// A span of `file` covering bytes `start..end`.
+pub fn span(file: &str, start: u32, end: u32) -> SourceSpan {
+    SourceSpan {
+        file: PathBuf::from(file),
+        start,
+        end,
+    }
+}
+
+/// Project and report for `tests/fixtures/simple_noir`, with `outcomes` assigned to the
+/// discovered mutants in id order (the rest stay not run) and a passing baseline.
+pub fn fixture_report(outcomes: &[MutantOutcome]) -> (Project, MutationRunReport) {
+    let root = PathBuf::from("tests/fixtures/simple_noir");
+    let project = Project::from_root(root.clone()).expect("fixture project should load");
+    let mut mutants = discover_mutants(&project);
+    for (m, outcome) in mutants.iter_mut().zip(outcomes) {
+        m.outcome = outcome.clone();
+    }
+
+    let mut summary = RunSummary::default();
+    for m in &mutants {
+        match m.outcome {
+            MutantOutcome::Killed => summary.killed += 1,
+            MutantOutcome::Survived => summary.survived += 1,
+            MutantOutcome::Invalid => summary.invalid += 1,
+            MutantOutcome::Timeout => summary.timeout += 1,
+            MutantOutcome::NotRun => {}
+        }
+    }
+
+    let executed = mutants
+        .iter()
+        .filter(|m| m.outcome != MutantOutcome::NotRun)
+        .count();
+    let report = MutationRunReport::success(
+        root,
+        mutants.len(),
+        executed,
+        BaselineReport {
+            success: true,
+            exit_code: Some(0),
+            duration_ms: 0,
+        },
+        summary,
+        mutants,
+    );
+    (project, report)
+}
@@ -19,6 +19,7 @@ pub struct Ui {
     progress_killed: u64,
     progress_survived: u64,
     progress_invalid: u64,
+    progress_timeout: u64,
     runner_errors: u64,
 }
 
@@ -44,6 +45,7 @@ impl Ui {
             progress_killed: 0,
             progress_survived: 0,
             progress_invalid: 0,
+            progress_timeout: 0,
             runner_errors: 0,
         }
     }
@@ -60,6 +62,7 @@ impl Ui {
             progress_killed: 0,
             progress_survived: 0,
             progress_invalid: 0,
+            progress_timeout: 0,
             runner_errors: 0,
         }
     }
@@ -122,6 +125,9 @@ impl Ui {
             MutantOutcome::Invalid => {
                 self.progress_invalid = self.progress_invalid.saturating_add(1)
             }
+            MutantOutcome::Timeout => {
+                self.progress_timeout = self.progress_timeout.saturating_add(1)
+            }
             MutantOutcome::NotRun => return,
         }
 
@@ -136,6 +142,12 @@ impl Ui {
                         m.id
                     ));
                 }
+                MutantOutcome::Timeout => {
+                    self.line(format!(
+                        "mutant {} timed out (tests did not finish in time)",
+                        m.id
+                    ));
+                }
                 _ => {}
             }
             return;
@@ -145,6 +157,7 @@ impl Ui {
             MutantOutcome::Killed => style("KILLED").red().bold(),
             MutantOutcome::Survived => style("SURVIVED").green().bold(),
             MutantOutcome::Invalid => style("INVALID").yellow().bold(),
+            MutantOutcome::Timeout => style("TIMEOUT").magenta().bold(),
             MutantOutcome::NotRun => return,
         };
 
@@ -195,6 +208,7 @@ mod tests {
             progress_killed: 0,
             progress_survived: 0,
             progress_invalid: 0,
+            progress_timeout: 0,
             runner_errors: 0,
         };
 
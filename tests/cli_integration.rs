@@ -39,10 +39,16 @@ count=$((count + 1))
 echo "$count" > "$count_file"
 
 if [[ -n "${ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL-}" && "$count" -eq "${ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL}" ]]; then
+  echo "[simple_noir] Testing test_main... FAIL"
   echo "fake nargo: failing on call $count" >&2
   exit 1
 fi
 
+# Optional: hang on a specific call number (exercises `--timeout`).
+if [[ -n "${ZK_MUTANT_FAKE_NARGO_SLEEP_ON_CALL-}" && "$count" -eq "${ZK_MUTANT_FAKE_NARGO_SLEEP_ON_CALL}" ]]; then
+  exec sleep 30
+fi
+
 if [[ "${ZK_MUTANT_FAKE_NARGO_FAIL-}" == "1" ]]; then
   echo "fake nargo: failing as requested" >&2
   exit 1
@@ -84,11 +90,18 @@ echo %COUNT%>"%COUNTFILE%"
 
 if not "%ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL%"=="" (
   if "%COUNT%"=="%ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL%" (
+    echo [simple_noir] Testing test_main... FAIL
     echo fake nargo: failing on call %COUNT% 1>&2
     exit /b 1
   )
 )
 
+if not "%ZK_MUTANT_FAKE_NARGO_SLEEP_ON_CALL%"=="" (
+  if "%COUNT%"=="%ZK_MUTANT_FAKE_NARGO_SLEEP_ON_CALL%" (
+    ping -n 31 127.0.0.1 >nul
+  )
+)
+
 if "%ZK_MUTANT_FAKE_NARGO_FAIL%"=="1" (
   echo fake nargo: failing as requested 1>&2
   exit /b 1
@@ -149,7 +162,9 @@ fn run_zk_mutant(args: &[&str], envs: &[(&str, &str)]) -> String {
     cmd.args(args)
         .env("PATH", new_path)
         .env("NO_COLOR", "1")
-        .env("RUST_BACKTRACE", "0");
+        .env("RUST_BACKTRACE", "0")
+        // Never append to the real step summary when the suite runs in GitHub Actions.
+        .env_remove("GITHUB_STEP_SUMMARY");
 
     if args.first() == Some(&"run") && !has_flag(args, "--out-dir") {
         cmd.args(["--out-dir", &out_dir_str]);
@@ -184,7 +199,9 @@ fn run_zk_mutant_stdout(args: &[&str], envs: &[(&str, &str)]) -> String {
     cmd.args(args)
         .env("PATH", new_path)
         .env("NO_COLOR", "1")
-        .env("RUST_BACKTRACE", "0");
+        .env("RUST_BACKTRACE", "0")
+        // Never append to the real step summary when the suite runs in GitHub Actions.
+        .env_remove("GITHUB_STEP_SUMMARY");
 
     if args.first() == Some(&"run") && !has_flag(args, "--out-dir") {
         cmd.args(["--out-dir", &out_dir_str]);
@@ -214,7 +231,9 @@ fn run_zk_mutant_with_out_dir(
     cmd.args(args)
         .env("PATH", new_path)
         .env("NO_COLOR", "1")
-        .env("RUST_BACKTRACE", "0");
+        .env("RUST_BACKTRACE", "0")
+        // Never append to the real step summary when the suite runs in GitHub Actions.
+        .env_remove("GITHUB_STEP_SUMMARY");
 
     if args.first() == Some(&"run") && !has_flag(args, "--out-dir") {
         cmd.args(["--out-dir", &out_dir_str]);
@@ -239,6 +258,15 @@ fn scan_fixture_snapshot() {
     insta::assert_snapshot!("scan_fixture", out);
 }
 
+#[test]
+fn scan_fixture_json_snapshot() {
+    let out = run_zk_mutant_stdout(
+        &["scan", "--project", "tests/fixtures/simple_noir", "--json"],
+        &[],
+    );
+    insta::assert_snapshot!("scan_fixture_json", out);
+}
+
 #[test]
 fn preflight_fixture_snapshot() {
     let out = run_zk_mutant(
@@ -470,7 +498,14 @@ fn run_writes_out_dir_artifacts() {
 
     let outcomes_json =
         fs::read_to_string(out_dir.join("outcomes.json")).expect("read outcomes.json");
-    let _: Value = serde_json::from_str(&outcomes_json).expect("outcomes.json parses");
+    let outcomes: Value = serde_json::from_str(&outcomes_json).expect("outcomes.json parses");
+
+    // Mutant records carry line/column and the source line before and after mutation.
+    let location = &outcomes["mutants"][0]["location"];
+    assert_eq!(location["start"]["line"], 4);
+    assert_eq!(location["start"]["column"], 14);
+    assert_eq!(location["source_line"], "    assert(x < y);");
+    assert_eq!(location["mutated_line"], "    assert(x >= y);");
 }
 
 #[test]
@@ -575,3 +610,1216 @@ fn run_prints_killed_line_when_mutant_fails_tests() {
         "expected killed line, got:\n{out}"
     );
 }
+
+#[test]
+fn run_reuses_cached_outcomes_on_second_run() {
+    let out_td = TempDir::new().expect("TempDir for out-dir should create");
+    let out_dir = out_td.path().join("mutants.out");
+    let args = [
+        "run",
+        "--project",
+        "tests/fixtures/simple_noir",
+        "--limit",
+        "2",
+        "--json",
+        "--out-dir",
+        &out_dir.to_string_lossy(),
+    ];
+
+    let first = run_zk_mutant_with_out_dir(&args, &[], &out_dir);
+    assert!(first.status.success(), "first run should succeed");
+    assert!(
+        out_td.path().join("mutants.out.cache.json").exists(),
+        "expected cache file next to the out dir"
+    );
+
+    // Every mutant run fails from call 2 onwards, so a cache miss would flip outcomes to killed.
+    let second = run_zk_mutant_with_out_dir(
+        &args,
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(second.status.success(), "second run should succeed");
+
+    let report: Value = serde_json::from_slice(&second.stdout).expect("stdout is JSON");
+    assert_eq!(report["cache"]["hits"], 2);
+    assert_eq!(report["cache"]["misses"], 0);
+    assert_eq!(report["summary"]["survived"], 2);
+}
+
+#[test]
+fn run_no_cache_omits_cache_stats() {
+    let out_td = TempDir::new().expect("TempDir for out-dir should create");
+    let out_dir = out_td.path().join("mutants.out");
+
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--json",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+    assert!(out.status.success(), "run should succeed");
+
+    let report: Value = serde_json::from_slice(&out.stdout).expect("stdout is JSON");
+    assert!(report.get("cache").is_none());
+    assert!(!out_td.path().join("mutants.out.cache.json").exists());
+}
+
+#[test]
+fn run_survivors_baseline_only_fails_on_new_survivors() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+    let baseline = td.path().join("survivors.json");
+    let baseline_str = baseline.to_string_lossy().to_string();
+    let out_dir_str = out_dir.to_string_lossy().to_string();
+
+    // Accept the survivors of the first mutant only.
+    let update = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--no-cache",
+            "--survivors-baseline",
+            &baseline_str,
+            "--update-baseline",
+            "--out-dir",
+            &out_dir_str,
+        ],
+        &[],
+        &out_dir,
+    );
+    assert!(update.status.success(), "update run should succeed");
+
+    let written: Value =
+        serde_json::from_str(&fs::read_to_string(&baseline).expect("read baseline"))
+            .expect("baseline parses");
+    assert_eq!(written["survivors"].as_array().map(|a| a.len()), Some(1));
+
+    // Same mutant: accepted, so --fail-on-survivors passes.
+    let accepted = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--no-cache",
+            "--fail-on-survivors",
+            "--survivors-baseline",
+            &baseline_str,
+            "--json",
+            "--out-dir",
+            &out_dir_str,
+        ],
+        &[],
+        &out_dir,
+    );
+    assert_eq!(accepted.status.code(), Some(0));
+    let report: Value = serde_json::from_slice(&accepted.stdout).expect("stdout is JSON");
+    assert_eq!(report["known_survivors"]["accepted"], 1);
+    assert_eq!(report["known_survivors"]["new"], 0);
+
+    // A second survivor is not in the baseline: exit code 2.
+    let new = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--fail-on-survivors",
+            "--survivors-baseline",
+            &baseline_str,
+            "--out-dir",
+            &out_dir_str,
+        ],
+        &[],
+        &out_dir,
+    );
+    assert_eq!(new.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&new.stderr).contains("1 new mutant(s) survived"),
+        "expected new-survivor policy message"
+    );
+}
+
+#[test]
+fn run_missing_survivors_baseline_is_an_error() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+    let missing = td.path().join("missing.json");
+
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--survivors-baseline",
+            &missing.to_string_lossy(),
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn compare_matches_mutants_across_runs() {
+    let td = TempDir::new().expect("TempDir should create");
+    let old_dir = td.path().join("old.out");
+    let new_dir = td.path().join("new.out");
+
+    // Old run: first mutant killed. New run: everything survives.
+    let old = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &old_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &old_dir,
+    );
+    assert!(old.status.success());
+
+    let new = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--no-cache",
+            "--out-dir",
+            &new_dir.to_string_lossy(),
+        ],
+        &[],
+        &new_dir,
+    );
+    assert!(new.status.success());
+
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "compare",
+            &old_dir.join("run.json").to_string_lossy(),
+            &new_dir.join("run.json").to_string_lossy(),
+            "--json",
+        ],
+        &[],
+        &new_dir,
+    );
+    assert!(out.status.success());
+
+    let cmp: Value = serde_json::from_slice(&out.stdout).expect("stdout is JSON");
+    assert_eq!(cmp["newly_surviving"].as_array().map(|a| a.len()), Some(1));
+    assert_eq!(cmp["newly_killed"].as_array().map(|a| a.len()), Some(0));
+    assert_eq!(cmp["added"].as_array().map(|a| a.len()), Some(2));
+    assert_eq!(cmp["removed"].as_array().map(|a| a.len()), Some(0));
+    assert_eq!(cmp["old_score"], 50.0);
+    assert_eq!(cmp["new_score"], 0.0);
+    assert_eq!(cmp["score_delta"], -50.0);
+}
+
+#[test]
+fn run_min_score_failure_exits_3_and_explains() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives: score 50%.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--min-score",
+            "80",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+
+    assert_eq!(out.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("mutation score 50.00% is below the minimum 80.00%"),
+        "expected min-score explanation, got:\n{stderr}"
+    );
+
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("run.json")).unwrap()).unwrap();
+    assert_eq!(report["policy_violations"][0]["kind"], "min_score");
+}
+
+#[test]
+fn run_per_path_threshold_from_config() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+    let config = td.path().join("zk-mutant.json");
+    fs::write(
+        &config,
+        r#"{ "thresholds": { "paths": { "src/": 90, "lib/": 90 } } }"#,
+    )
+    .unwrap();
+
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--no-cache",
+            "--config",
+            &config.to_string_lossy(),
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+
+    assert_eq!(out.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("mutation score 0.00% in src/ is below the minimum 90.00%"),
+        "expected per-path explanation, got:\n{stderr}"
+    );
+    assert!(!stderr.contains("lib/"), "paths without mutants never fail");
+}
+
+#[test]
+fn run_timeout_records_outcome_and_fails_on_timeout() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--no-cache",
+            "--timeout",
+            "1",
+            "--fail-on-timeout",
+            "--json",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_SLEEP_ON_CALL", "2")],
+        &out_dir,
+    );
+
+    assert_eq!(out.status.code(), Some(4));
+    let report: Value = serde_json::from_slice(&out.stdout).expect("stdout is JSON");
+    assert_eq!(report["summary"]["timeout"], 1);
+    assert_eq!(report["mutants"][0]["outcome"], "Timeout");
+    assert_eq!(report["policy_violations"][0]["kind"], "timeout");
+
+    let timeouts = fs::read_to_string(out_dir.join("timeout.txt")).expect("timeout.txt");
+    assert_eq!(timeouts.lines().count(), 1);
+}
+
+#[test]
+fn run_writes_unified_diffs_that_git_apply_accepts() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--no-cache",
+            "--diff-context",
+            "1",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let diff_path = out_dir.join("diff").join("000001.diff");
+    let diff = fs::read_to_string(&diff_path).expect("read diff");
+    assert!(
+        diff.starts_with("#1 src/"),
+        "description line first:\n{diff}"
+    );
+    assert!(diff.contains("\ndiff --git a/src/"), "git header:\n{diff}");
+    assert!(diff.contains("\n--- a/src/") && diff.contains("\n+++ b/src/"));
+    assert!(diff.contains("\n@@ -"), "hunk header:\n{diff}");
+
+    // The diff applies cleanly to a copy of the project (when git is available).
+    let copy = td.path().join("copy");
+    fs::create_dir_all(copy.join("src")).unwrap();
+    for name in ["main.nr", "utils.nr"] {
+        fs::copy(
+            Path::new("tests/fixtures/simple_noir/src").join(name),
+            copy.join("src").join(name),
+        )
+        .unwrap();
+    }
+    if let Ok(status) = std::process::Command::new("git")
+        .args(["apply", "--check"])
+        .arg(&diff_path)
+        .current_dir(&copy)
+        .status()
+    {
+        assert!(status.success(), "git apply --check failed for:\n{diff}");
+    }
+}
+
+#[test]
+fn run_writes_self_contained_html_report() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed (with a failing test name in nargo's output), second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let index = fs::read_to_string(out_dir.join("report/index.html")).expect("index.html");
+    assert!(index.contains("mutation score: 50.00%"), "index:\n{index}");
+    assert!(
+        index.contains("href=\"src/main.nr.html\""),
+        "index:\n{index}"
+    );
+
+    let page = fs::read_to_string(out_dir.join("report/src/main.nr.html")).expect("file page");
+    assert!(page.contains("class=\"mut killed\""), "page:\n{page}");
+    assert!(page.contains("killed by: test_main"), "page:\n{page}");
+    assert!(page.contains("<a href=\"../index.html\">"), "page:\n{page}");
+    for html in [&index, &page] {
+        assert!(!html.contains("http://") && !html.contains("https://"));
+        assert!(!html.contains("<script src") && !html.contains("<link"));
+    }
+
+    let run: Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("run.json")).unwrap()).unwrap();
+    assert_eq!(run["mutants"][0]["killed_by"][0], "test_main");
+}
+
+#[test]
+fn run_writes_sarif_for_survivors() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+    let extra = td.path().join("ci").join("zk.sarif");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--sarif",
+            &extra.to_string_lossy(),
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    for path in [out_dir.join("mutants.sarif"), extra] {
+        let sarif: Value = serde_json::from_str(&fs::read_to_string(&path).expect("read sarif"))
+            .expect("sarif parses");
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let results = sarif["runs"][0]["results"].as_array().expect("results");
+        assert_eq!(results.len(), 1, "only the survivor is reported");
+        let loc = &results[0]["locations"][0]["physicalLocation"];
+        assert!(
+            loc["artifactLocation"]["uri"]
+                .as_str()
+                .unwrap()
+                .starts_with("src/")
+        );
+        assert!(loc["region"]["startLine"].as_u64().unwrap() >= 1);
+    }
+}
+
+#[test]
+fn run_writes_junit_xml() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let xml = fs::read_to_string(out_dir.join("junit.xml")).expect("read junit.xml");
+    assert!(xml.starts_with("<?xml"), "{xml}");
+    assert!(
+        xml.contains("tests=\"4\" failures=\"1\" errors=\"0\" skipped=\"2\""),
+        "{xml}"
+    );
+    assert_eq!(xml.matches("<testcase ").count(), 4, "{xml}");
+    assert!(xml.contains("<failure type=\"survived\""), "{xml}");
+    assert!(
+        xml.contains("diff --git a/src/"),
+        "failure carries the diff:\n{xml}"
+    );
+}
+
+#[test]
+fn run_junit_lists_mutants_cut_by_limit_as_skipped() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // The fake nargo passes, so the one executed mutant survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "1",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let xml = fs::read_to_string(out_dir.join("junit.xml")).expect("read junit.xml");
+    assert!(
+        xml.contains("tests=\"4\" failures=\"1\" errors=\"0\" skipped=\"3\""),
+        "{xml}"
+    );
+    assert_eq!(
+        xml.matches("<skipped message=\"mutant not run\"/>").count(),
+        3,
+        "{xml}"
+    );
+}
+
+#[test]
+fn run_writes_mutation_testing_report_schema_json() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed (by `test_main`), second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let report: Value = serde_json::from_slice(
+        &fs::read(out_dir.join("mutation-report.json")).expect("read mutation-report.json"),
+    )
+    .expect("mutation-report.json should be JSON");
+
+    assert_eq!(report["schemaVersion"], "2");
+    let file = &report["files"]["src/main.nr"];
+    assert!(file["source"].as_str().unwrap().contains("fn main"));
+
+    let mutants = file["mutants"].as_array().unwrap();
+    assert_eq!(mutants.len(), 2);
+    assert_eq!(mutants[0]["status"], "Killed");
+    assert_eq!(mutants[0]["killedBy"], serde_json::json!(["test_main"]));
+    assert_eq!(mutants[1]["status"], "Survived");
+    assert!(mutants[0]["location"]["start"]["line"].as_u64().unwrap() >= 1);
+
+    assert_eq!(
+        report["testFiles"]["src/main.nr"]["tests"][0]["id"],
+        "test_main"
+    );
+}
+
+#[test]
+fn run_writes_cargo_mutants_outcomes_json_when_requested() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--outcomes-format",
+            "cargo-mutants",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let outcomes: Value = serde_json::from_slice(
+        &fs::read(out_dir.join("outcomes.json")).expect("read outcomes.json"),
+    )
+    .expect("outcomes.json should be JSON");
+
+    assert_eq!(outcomes["total_mutants"], 2);
+    assert_eq!(outcomes["caught"], 1);
+    assert_eq!(outcomes["missed"], 1);
+    assert_eq!(outcomes["success"], 1);
+
+    let scenarios = outcomes["outcomes"].as_array().unwrap();
+    assert_eq!(scenarios.len(), 3);
+    assert_eq!(scenarios[0]["scenario"], "Baseline");
+    assert_eq!(scenarios[1]["summary"], "CaughtMutant");
+    assert_eq!(scenarios[1]["scenario"]["Mutant"]["package"], "simple_noir");
+    assert_eq!(scenarios[1]["phase_results"][0]["phase"], "Test");
+    assert_eq!(
+        scenarios[1]["phase_results"][0]["process_status"]["Failure"],
+        1
+    );
+    assert!(outcomes["start_time"].as_str().unwrap().ends_with('Z'));
+    assert!(outcomes["end_time"].as_str().unwrap() >= outcomes["start_time"].as_str().unwrap());
+    assert_eq!(scenarios[2]["summary"], "MissedMutant");
+    assert_eq!(scenarios[2]["diff_path"], "diff/000002.diff");
+    assert!(out_dir.join("diff/000002.diff").exists());
+}
+
+#[test]
+fn run_writes_markdown_summary_and_appends_step_summary() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+    let md_path = td.path().join("summary/mutation.md");
+    let step_summary = td.path().join("step_summary.md");
+    fs::write(&step_summary, "# earlier step\n").unwrap();
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--markdown",
+            &md_path.to_string_lossy(),
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[
+            ("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2"),
+            ("GITHUB_STEP_SUMMARY", &step_summary.to_string_lossy()),
+        ],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let md = fs::read_to_string(&md_path).expect("read Markdown summary");
+    assert!(md.contains("![mutation score]"), "{md}");
+    assert!(
+        md.contains("| `src/main.nr` | 50.00% | 1 | 1 | 0 | 0 |"),
+        "{md}"
+    );
+    assert!(md.contains("<summary>1 surviving mutant</summary>"), "{md}");
+    assert!(md.contains("```diff"), "{md}");
+
+    let summary = fs::read_to_string(&step_summary).expect("read step summary");
+    assert!(summary.starts_with("# earlier step\n"), "{summary}");
+    assert!(summary.contains(&md), "{summary}");
+}
+
+#[test]
+fn run_prints_annotations_for_survivors() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--annotations",
+            "github",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let annotations: Vec<&str> = stdout
+        .lines()
+        .filter(|l| l.starts_with("::warning "))
+        .collect();
+    assert_eq!(annotations.len(), 1, "{stdout}");
+    assert!(
+        annotations[0]
+            .starts_with("::warning file=tests/fixtures/simple_noir/src/main.nr,line=18,col=14,"),
+        "{stdout}"
+    );
+
+    // `--json` keeps stdout machine-readable; compiler-style lines go to stderr.
+    let json_out_dir = td.path().join("json.out");
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--json",
+            "--annotations",
+            "compiler",
+            "--out-dir",
+            &json_out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &json_out_dir,
+    );
+    let _: Value = serde_json::from_slice(&out.stdout).expect("stdout should stay JSON");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr
+            .contains("tests/fixtures/simple_noir/src/main.nr:18:14: warning: surviving mutant #2"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn run_writes_per_mutant_logs_and_repro_scripts() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--max-log-bytes",
+            "18",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let killed = out_dir.join("mutants/000001");
+    let log = fs::read_to_string(killed.join("nargo.log")).expect("read nargo.log");
+    assert!(
+        log.contains("outcome: killed\ncommand: nargo test\nexit_code: 1\n"),
+        "{log}"
+    );
+    assert!(
+        log.contains("bytes omitted ...]\ntest_main... FAIL\n"),
+        "{log}"
+    );
+    assert!(log.contains("--- stderr ---\n[... "), "{log}");
+
+    let mutated = fs::read_to_string(killed.join("main.nr")).expect("read mutated file");
+    assert!(mutated.contains("assert(x >= y);"), "{mutated}");
+    let diff = fs::read_to_string(killed.join("mutant.diff")).expect("read mutant.diff");
+    assert!(
+        diff.starts_with("diff --git a/src/main.nr b/src/main.nr\n"),
+        "{diff}"
+    );
+
+    let survived = out_dir.join("mutants/000002");
+    assert!(survived.join("repro.sh").exists());
+    assert!(
+        !out_dir.join("mutants/000003").exists(),
+        "not-run mutants get no dir"
+    );
+
+    #[cfg(unix)]
+    {
+        let fake_nargo = make_fake_nargo_dir();
+        let repro = std::process::Command::new("bash")
+            .arg(survived.join("repro.sh"))
+            .env("PATH", prepend_path(fake_nargo.path()))
+            .output()
+            .expect("repro.sh should run");
+        assert!(repro.status.success(), "{repro:?}");
+        assert_eq!(String::from_utf8_lossy(&repro.stdout), "fake nargo: ok\n");
+
+        let stderr = String::from_utf8_lossy(&repro.stderr);
+        let work = stderr
+            .trim()
+            .strip_prefix("mutated tree: ")
+            .expect("repro.sh reports the mutated tree");
+        let copied = fs::read_to_string(Path::new(work).join("src/main.nr")).unwrap();
+        assert!(copied.contains("assert(x == y);"), "{copied}");
+        let _ = fs::remove_dir_all(work);
+    }
+}
+
+#[test]
+fn report_rerenders_run_json_with_filters() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+    let run_json = out_dir.join("run.json");
+    let run_json = run_json.to_string_lossy();
+
+    let text = run_zk_mutant(&["report", "--from", &run_json], &[]);
+    assert!(text.starts_with("status: exit status: 0\n"), "{text}");
+    assert!(
+        text.contains("mutants killed:   1\nmutants survived: 1\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("--- surviving mutants (1 of 2) ---"),
+        "{text}"
+    );
+
+    // Filtering recomputes the summary from the remaining mutants.
+    let json = run_zk_mutant_stdout(
+        &[
+            "report",
+            "--from",
+            &run_json,
+            "--format",
+            "json",
+            "--outcome",
+            "survived",
+        ],
+        &[],
+    );
+    let filtered: Value = serde_json::from_str(&json).expect("report JSON");
+    assert_eq!(filtered["mutants"].as_array().unwrap().len(), 1);
+    assert_eq!(filtered["summary"]["killed"], 0);
+    assert_eq!(filtered["summary"]["survived"], 1);
+
+    let none = run_zk_mutant_stdout(
+        &[
+            "report",
+            "--from",
+            &run_json,
+            "--format",
+            "json",
+            "--file",
+            "src/utils",
+        ],
+        &[],
+    );
+    let none: Value = serde_json::from_str(&none).expect("report JSON");
+    assert!(none["mutants"].as_array().unwrap().is_empty());
+
+    // `--file` matches whole path components: `src/mai` is not a prefix of `src/main.nr`.
+    let file_count = |filter: &str| {
+        let json = run_zk_mutant_stdout(
+            &[
+                "report", "--from", &run_json, "--format", "json", "--file", filter,
+            ],
+            &[],
+        );
+        let report: Value = serde_json::from_str(&json).expect("report JSON");
+        report["mutants"].as_array().unwrap().len()
+    };
+    assert_eq!(file_count("src/mai"), 0);
+    assert_eq!(file_count("src/main.nr"), 2);
+    assert_eq!(file_count("src"), 2);
+
+    let junit_path = td.path().join("again/junit.xml");
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "report",
+            "--from",
+            &run_json,
+            "--format",
+            "junit",
+            "--output",
+            &junit_path.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+    assert!(out.status.success());
+    assert_eq!(
+        fs::read_to_string(&junit_path).unwrap(),
+        fs::read_to_string(out_dir.join("junit.xml")).unwrap()
+    );
+
+    let html_dir = td.path().join("html");
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "report",
+            "--from",
+            &run_json,
+            "--format",
+            "html",
+            "--output",
+            &html_dir.to_string_lossy(),
+        ],
+        &[],
+        &out_dir,
+    );
+    assert!(out.status.success());
+    assert!(html_dir.join("report/index.html").exists());
+}
+
+#[test]
+fn report_junit_not_run_filter_keeps_only_mutants_cut_by_limit() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // Two of the four mutants run (first killed, second survives); two are cut.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+    let run_json = out_dir.join("run.json");
+
+    let xml = run_zk_mutant_stdout(
+        &[
+            "report",
+            "--from",
+            &run_json.to_string_lossy(),
+            "--format",
+            "junit",
+            "--outcome",
+            "not_run",
+        ],
+        &[],
+    );
+    assert!(
+        xml.contains("tests=\"2\" failures=\"0\" errors=\"0\" skipped=\"2\""),
+        "{xml}"
+    );
+    assert!(!xml.contains("#1\""), "{xml}");
+    assert!(!xml.contains("#2\""), "{xml}");
+}
+
+#[test]
+fn report_rejects_unknown_outcome() {
+    let out = run_zk_mutant(&["report", "--from", "run.json", "--outcome", "maybe"], &[]);
+    assert!(out.contains("unknown outcome `maybe`"), "{out}");
+}
+
+#[test]
+fn show_prints_mutant_in_context() {
+    let out = run_zk_mutant_stdout(
+        &[
+            "show",
+            "2",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--context",
+            "1",
+        ],
+        &[],
+    );
+    assert!(
+        out.contains("17 |\n18 |     assert(x != y);\n   |              ^^\n19 |"),
+        "{out}"
+    );
+    assert!(
+        out.contains("mutated:\n18 |     assert(x == y);\n"),
+        "{out}"
+    );
+
+    let out = run_zk_mutant(
+        &["show", "42", "--project", "tests/fixtures/simple_noir"],
+        &[],
+    );
+    assert!(
+        out.contains("no mutant with id or fingerprint `42`"),
+        "{out}"
+    );
+}
+
+#[test]
+fn apply_and_revert_round_trip_in_working_tree() {
+    let td = TempDir::new().expect("TempDir should create");
+    for rel in ["Nargo.toml", "src/main.nr", "src/utils.nr"] {
+        let to = td.path().join(rel);
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::copy(Path::new("tests/fixtures/simple_noir").join(rel), to).unwrap();
+    }
+    let project = td.path().to_string_lossy().to_string();
+    let utils = td.path().join("src/utils.nr");
+    let before = fs::read_to_string(&utils).unwrap();
+
+    let out = run_zk_mutant(&["apply", "3", "--project", &project], &[]);
+    assert!(out.starts_with("status: exit status: 0\n"), "{out}");
+    assert!(out.contains("applied #3 src/utils.nr:4:18"), "{out}");
+    assert_ne!(fs::read_to_string(&utils).unwrap(), before);
+    assert!(td.path().join(".zk-mutant-applied.json").exists());
+
+    let out = run_zk_mutant(&["apply", "1", "--project", &project], &[]);
+    assert!(out.contains("mutant #3 is already applied"), "{out}");
+
+    // Other commands notice the mutated working tree: `run` refuses, the rest warn.
+    let out = run_zk_mutant(&["run", "--project", &project], &[]);
+    assert!(!out.starts_with("status: exit status: 0\n"), "{out}");
+    assert!(
+        out.contains("refusing to run: mutant #3 is applied to \"src/utils.nr\"; undo it with `zk-mutant apply --revert`"),
+        "{out}"
+    );
+    let patches = td.path().join("patches").to_string_lossy().to_string();
+    for args in [
+        vec!["list", "--project", &project],
+        vec!["scan", "--project", &project],
+        vec!["show", "1", "--project", &project],
+        vec![
+            "export",
+            "--ids",
+            "1",
+            "--project",
+            &project,
+            "--out",
+            &patches,
+        ],
+    ] {
+        let out = run_zk_mutant(&args, &[]);
+        assert!(out.starts_with("status: exit status: 0\n"), "{out}");
+        assert!(
+            out.contains("warning: mutant #3 is applied to \"src/utils.nr\""),
+            "{out}"
+        );
+    }
+
+    let out = run_zk_mutant(&["apply", "--revert", "--project", &project], &[]);
+    assert!(out.contains("reverted mutant #3 in src/utils.nr"), "{out}");
+    assert_eq!(fs::read_to_string(&utils).unwrap(), before);
+    assert!(!td.path().join(".zk-mutant-applied.json").exists());
+}
+
+#[test]
+fn export_writes_format_patches_that_git_am_accepts() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let patches = td.path().join("patches");
+    let out = run_zk_mutant(
+        &[
+            "export",
+            "--from",
+            &out_dir.join("run.json").to_string_lossy(),
+            "--outcome",
+            "survived",
+            "--out",
+            &patches.to_string_lossy(),
+        ],
+        &[],
+    );
+    assert!(out.contains("wrote 1 patch to "), "{out}");
+    let patch_path = patches.join("0001-Mutant-2-src-main.nr-18-neq_to_eq.patch");
+    let patch = fs::read_to_string(&patch_path).expect("read patch");
+    assert!(
+        patch.contains("\nSubject: [PATCH] Mutant #2 src/main.nr:18 neq_to_eq\n"),
+        "{patch}"
+    );
+    assert!(patch.contains("\nOutcome: survived\n"), "{patch}");
+
+    // Without --from, ids refer to freshly discovered mutants.
+    let all = td.path().join("all");
+    let out = run_zk_mutant(
+        &[
+            "export",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--ids",
+            "1,3",
+            "--out",
+            &all.to_string_lossy(),
+        ],
+        &[],
+    );
+    assert!(out.contains("wrote 2 patches to "), "{out}");
+    assert!(
+        all.join("0002-Mutant-3-src-utils.nr-4-eq_to_neq.patch")
+            .exists()
+    );
+
+    let out = run_zk_mutant(&["export", "--outcome", "survived"], &[]);
+    assert!(out.contains("--from <FILE>"), "{out}");
+
+    // The patch applies as a commit to a git copy of the project (when git is available).
+    let copy = td.path().join("copy");
+    fs::create_dir_all(copy.join("src")).unwrap();
+    for name in ["main.nr", "utils.nr"] {
+        fs::copy(
+            Path::new("tests/fixtures/simple_noir/src").join(name),
+            copy.join("src").join(name),
+        )
+        .unwrap();
+    }
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(&copy)
+            .output()
+    };
+    if git(&["init", "-q"]).is_ok_and(|o| o.status.success()) {
+        assert!(git(&["add", "."]).unwrap().status.success());
+        assert!(git(&["commit", "-qm", "base"]).unwrap().status.success());
+        let am = git(&["am", "-q", &patch_path.to_string_lossy()]).unwrap();
+        assert!(am.status.success(), "git am failed: {am:?}");
+        assert!(
+            fs::read_to_string(copy.join("src/main.nr"))
+                .unwrap()
+                .contains("assert(x == y);")
+        );
+    }
+}
+
+#[test]
+fn run_writes_suggested_tests_for_survivors() {
+    let td = TempDir::new().expect("TempDir should create");
+    let out_dir = td.path().join("mutants.out");
+
+    // First mutant killed, second survives.
+    let out = run_zk_mutant_with_out_dir(
+        &[
+            "run",
+            "--project",
+            "tests/fixtures/simple_noir",
+            "--limit",
+            "2",
+            "--no-cache",
+            "--out-dir",
+            &out_dir.to_string_lossy(),
+        ],
+        &[("ZK_MUTANT_FAKE_NARGO_FAIL_ON_CALL", "2")],
+        &out_dir,
+    );
+    assert!(out.status.success());
+
+    let dir = out_dir.join("suggested_tests");
+    let names: Vec<String> = fs::read_dir(&dir)
+        .expect("suggested_tests dir")
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, ["000002_main.nr"]);
+
+    let stub = fs::read_to_string(dir.join("000002_main.nr")).unwrap();
+    assert!(
+        stub.contains("#[test]\nfn test_main_mutant_2() {\n    // TODO: inputs where `x != y`.\n"),
+        "{stub}"
+    );
+    assert!(
+        stub.contains("#[test(should_fail)]\nfn test_main_mutant_2_should_fail() {"),
+        "{stub}"
+    );
+}